@@ -1,14 +1,16 @@
-#![recursion_limit = "256"]
+#![recursion_limit = "512"]
 
 extern crate proc_macro;
 
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Data, DataEnum, DeriveInput, Fields, Ident};
+use syn::parse::{ParseStream, Parser};
+use syn::{Data, DataEnum, DataStruct, DeriveInput, Fields, Ident, Token};
 
 /// Derive to implement the `Key` trait.
 ///
-/// Requires that `fixed_map` is in scope.
+/// The generated code refers to the crate as `::fixed_map`, use
+/// `#[key(crate = ...)]` if it is available under another path.
 ///
 /// This derive implements the `Key` trait for a given type.
 ///
@@ -43,8 +45,8 @@ use syn::{Data, DataEnum, DeriveInput, Fields, Ident};
 ///
 /// /// Implement storage for `KeyStorage`.
 /// impl<V> fixed_map::storage::Storage<Key, V> for KeyStorage<V> {
-///     fn get(&self, key: Key) -> Option<&V> {
-///         match *self {
+///     fn get(&self, key: &Key) -> Option<&V> {
+///         match key {
 ///             Key::First => self.f1.as_ref(),
 ///             Key::Second => self.f2.as_ref(),
 ///             Key::Third => self.f3.as_ref(),
@@ -57,29 +59,689 @@ use syn::{Data, DataEnum, DeriveInput, Fields, Ident};
 /// /// Implement the `Key` trait to point out storage.
 /// impl<V> fixed_map::key::Key<Key, V> for Key {
 ///     type Storage = KeyStorage<V>;
+///     type SetStorage = KeyStorage<()>;
 /// }
 /// ```
+///
+/// The derive also implements `fixed_map::key::Enumerable` and
+/// `fixed_map::key::TotalKey` for the enum, which can be used as long as the
+/// fields of every variant implement them as well.
+///
+/// # Variants with fields
+///
+/// Variants can have any number of fields, either unnamed or named. A variant
+/// with a single field uses the storage of that field, while a variant with
+/// several fields is stored like the equivalent tuple key, so that
+/// `Variant(A, B, C)` uses the storage of `(A, (B, C))`.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Side {
+///     Left,
+///     Right,
+/// }
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Key {
+///     Edge(Side, bool),
+///     Corner { side: Side, top: bool },
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::Edge(Side::Left, true), 1);
+/// map.insert(Key::Corner { side: Side::Right, top: false }, 2);
+///
+/// assert_eq!(map.get(Key::Edge(Side::Left, true)), Some(&1));
+/// assert_eq!(map.get(Key::Edge(Side::Left, false)), None);
+/// assert_eq!(map.get(Key::Corner { side: Side::Right, top: false }), Some(&2));
+/// ```
+///
+/// Keys don't have to be `Copy`, so a variant can hold an owned key like
+/// `String`. Lookups take the key by reference, and only a variant with
+/// several fields clones them into the tuple it is stored as.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Key)]
+/// enum Ident {
+///     SelfValue,
+///     Custom(String),
+/// }
+///
+/// let mut map = Map::new();
+/// let foo = Ident::Custom(String::from("foo"));
+/// map.insert(foo.clone(), 1);
+///
+/// assert_eq!(map.get(&foo), Some(&1));
+/// assert_eq!(map.get(&Ident::SelfValue), None);
+/// ```
+///
+/// # Generics
+///
+/// Keys can have lifetime and type parameters, which are carried over to the
+/// generated storage. The fields of each variant are required to be keys when
+/// the storage is used.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Slot<T> {
+///     Empty,
+///     Full(T),
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Slot::Full(true), 1);
+/// map.insert(Slot::Empty, 2);
+///
+/// assert_eq!(map.get(Slot::Full(true)), Some(&1));
+/// assert_eq!(map.get(Slot::Full(false)), None);
+/// assert_eq!(map.get(Slot::Empty), Some(&2));
+/// ```
+///
+/// # Structs
+///
+/// Structs whose fields all implement `Key` can also be used as keys. They are
+/// stored in a `fixed_map::storage::TupleStorage`, which stores the remaining
+/// fields in the storage of the first field, so lookups don't involve hashing
+/// unless a field does.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Layer {
+///     Top,
+///     Bottom,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Side {
+///     Left,
+///     Right,
+/// }
+///
+/// #[derive(Clone, Copy, Key)]
+/// struct Cell {
+///     layer: Layer,
+///     side: Side,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Cell { layer: Layer::Top, side: Side::Left }, 1);
+///
+/// assert_eq!(map.get(Cell { layer: Layer::Top, side: Side::Left }), Some(&1));
+/// assert_eq!(map.get(Cell { layer: Layer::Bottom, side: Side::Left }), None);
+/// ```
+///
+/// # Attributes
+///
+/// ## `#[key(bitset)]`
+///
+/// Enums which only have unit variants can use a bitset as their storage
+/// when used in a `Set`. The bitset is backed by the smallest unsigned integer
+/// which fits all variants, or by `[u64; N]` for enums with more than 128
+/// variants.
+///
+/// ```rust
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Clone, Copy, Key)]
+/// #[key(bitset)]
+/// pub enum Key {
+///     First,
+///     Second,
+///     Third,
+/// }
+///
+/// let mut set = Set::new();
+/// set.insert(Key::Second);
+///
+/// assert!(set.contains(Key::Second));
+/// assert_eq!(std::mem::size_of_val(&set), 1);
+/// ```
+///
+/// ## `#[key(crate = ...)]`
+///
+/// The path to the `fixed_map` crate, for when it is renamed in `Cargo.toml`
+/// or re-exported by another crate. Defaults to `::fixed_map`.
+///
+/// ```rust
+/// mod facade {
+///     pub use fixed_map as maps;
+/// }
+///
+/// use facade::maps::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// #[key(crate = facade::maps)]
+/// enum Key {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::First, 1);
+///
+/// assert_eq!(map.get(Key::First), Some(&1));
+/// ```
+///
+/// ## `#[key(inline = N)]`
+///
+/// Placed on a variant with fields, this stores the fields in a
+/// `fixed_map::storage::InlineMapStorage` with room for `N` keys instead of
+/// in the storage of their own `Key` implementation. The fields only have to
+/// implement `Clone`, `Eq` and `Hash`, and no allocation is involved, which
+/// makes keys like `u32` usable without the `alloc` feature.
+///
+/// Once the variant holds `N` keys, `Map::try_insert` hands back any new key
+/// and its value, while `Map::insert` panics.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+/// enum Key {
+///     First,
+///     #[key(inline = 2)]
+///     Number(u32),
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::First, 1);
+/// map.insert(Key::Number(10), 2);
+/// map.insert(Key::Number(20), 3);
+///
+/// assert_eq!(map.get(Key::Number(20)), Some(&3));
+/// assert!(map.try_insert(Key::Number(30), 4).is_err());
+/// assert_eq!(map.try_insert(Key::Number(10), 5), Ok(Some(2)));
+/// ```
 #[proc_macro_derive(Key, attributes(key))]
 pub fn storage_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = syn::parse_macro_input!(input as DeriveInput);
-    let gen = impl_storage(&ast);
-    gen.into()
+
+    match impl_storage(&ast) {
+        Ok(gen) => gen.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Attributes specified on the container with `#[key(...)]`.
+struct Attrs {
+    /// Use a bitset as the set storage, with the span of the attribute which
+    /// asked for it.
+    bitset: Option<Span>,
+    /// The path to the `fixed_map` crate, which prefixes every path in the
+    /// generated code.
+    krate: TokenStream,
+}
+
+/// A single attribute in `#[key(...)]`.
+enum Attr {
+    /// `bitset`.
+    Bitset(Ident),
+    /// `crate = path`.
+    Crate(Token![crate], syn::Path),
+    /// `inline = N`.
+    Inline(Ident, syn::Expr),
+}
+
+impl syn::parse::Parse for Attr {
+    fn parse(input: ParseStream) -> syn::Result<Attr> {
+        if input.peek(Token![crate]) {
+            let krate = input.parse::<Token![crate]>()?;
+
+            if !input.peek(Token![=]) {
+                return Err(syn::Error::new_spanned(krate, "expected `crate = path`"));
+            }
+
+            input.parse::<Token![=]>()?;
+            return Ok(Attr::Crate(krate, input.parse()?));
+        }
+
+        let ident = input.parse::<Ident>()?;
+
+        if ident == "bitset" {
+            return Ok(Attr::Bitset(ident));
+        }
+
+        if ident == "inline" {
+            if !input.peek(Token![=]) {
+                return Err(syn::Error::new_spanned(ident, "expected `inline = N`"));
+            }
+
+            input.parse::<Token![=]>()?;
+            return Ok(Attr::Inline(ident, input.parse()?));
+        }
+
+        Err(syn::Error::new_spanned(
+            ident,
+            "unsupported `key` attribute, expected `bitset`, `crate = path` or `inline = N`",
+        ))
+    }
+}
+
+/// Parse every attribute in the `#[key(...)]` attributes of an item.
+fn parse_key_attrs(attrs: &[syn::Attribute]) -> syn::Result<Vec<Attr>> {
+    let mut output = Vec::new();
+
+    for attr in attrs {
+        if !attr.path.is_ident("key") {
+            continue;
+        }
+
+        let parser = |input: ParseStream| {
+            if !input.peek(syn::token::Paren) {
+                return Err(syn::Error::new_spanned(attr, "expected `#[key(...)]`"));
+            }
+
+            let content;
+            syn::parenthesized!(content in input);
+            content.parse_terminated::<Attr, Token![,]>(syn::parse::Parse::parse)
+        };
+
+        output.extend(parser.parse2(attr.tts.clone())?);
+    }
+
+    Ok(output)
+}
+
+/// Parse the `#[key(...)]` attributes on the container.
+fn parse_attrs(ast: &DeriveInput) -> syn::Result<Attrs> {
+    let mut attrs = Attrs {
+        bitset: None,
+        krate: quote!(::fixed_map),
+    };
+
+    for attr in parse_key_attrs(&ast.attrs)? {
+        match attr {
+            Attr::Bitset(ident) => {
+                attrs.bitset = Some(ident.span());
+            }
+            Attr::Crate(_, path) => {
+                attrs.krate = quote!(#path);
+            }
+            Attr::Inline(ident, _) => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`inline` is only supported on enum variants",
+                ));
+            }
+        }
+    }
+
+    Ok(attrs)
+}
+
+/// Parse the `#[key(...)]` attributes on a variant, returning the capacity
+/// of the inline storage which is asked for by `inline = N`.
+fn parse_variant_attrs(variant: &syn::Variant) -> syn::Result<Option<syn::Expr>> {
+    let mut inline = None;
+
+    for attr in parse_key_attrs(&variant.attrs)? {
+        match attr {
+            Attr::Inline(ident, capacity) => {
+                if let Fields::Unit = variant.fields {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "`inline` is only supported on variants with fields",
+                    ));
+                }
+
+                inline = Some(capacity);
+            }
+            Attr::Bitset(ident) => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`bitset` is only supported on the enum itself, not on its variants",
+                ));
+            }
+            Attr::Crate(krate, _) => {
+                return Err(syn::Error::new_spanned(
+                    krate,
+                    "`crate` is only supported on the enum itself, not on its variants",
+                ));
+            }
+        }
+    }
+
+    Ok(inline)
 }
 
 /// Derive to implement the `Key` trait.
-fn impl_storage(ast: &DeriveInput) -> TokenStream {
+fn impl_storage(ast: &DeriveInput) -> syn::Result<TokenStream> {
+    let attrs = parse_attrs(ast)?;
+
     match ast.data {
-        Data::Enum(ref en) => return impl_storage_enum(ast, en),
-        _ => panic!("`Key` attribute is only supported on enums"),
+        Data::Enum(ref en) => impl_storage_enum(ast, en, &attrs),
+        Data::Struct(ref st) => impl_storage_struct(ast, st, &attrs),
+        Data::Union(ref un) => Err(syn::Error::new(
+            un.union_token.span,
+            "`Key` can't be derived for unions, only for enums and structs",
+        )),
+    }
+}
+
+/// Implement `Key` for structs.
+///
+/// Structs are stored in a `TupleStorage` by implementing `Product`, where the
+/// head is the first field and the tail is the remaining fields as nested
+/// pairs. Structs with fewer than two fields use `()` in place of the missing
+/// fields.
+fn impl_storage_struct(
+    ast: &DeriveInput,
+    st: &DataStruct,
+    attrs: &Attrs,
+) -> syn::Result<TokenStream> {
+    let ident = &ast.ident;
+    let krate = &attrs.krate;
+    let generics = KeyGenerics::new(ast)?;
+    let ty = &generics.ty;
+    let params = &generics.params;
+    let predicates = &generics.predicates;
+
+    if let Some(span) = attrs.bitset {
+        return Err(syn::Error::new(
+            span,
+            "`#[key(bitset)]` is only supported on enums",
+        ));
+    }
+
+    let mut types = Vec::new();
+    let mut bindings = Vec::new();
+    let mut members = Vec::new();
+
+    for (index, field) in st.fields.iter().enumerate() {
+        types.push(field.ty.clone());
+        bindings.push(Ident::new(&format!("f{}", index), Span::call_site()));
+
+        members.push(match field.ident {
+            Some(ref ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(index);
+                quote!(#index)
+            }
+        });
+    }
+
+    let constructor = {
+        let bindings = &bindings;
+
+        match st.fields {
+            Fields::Named(..) => quote!(#ident { #(#members: #bindings),* }),
+            Fields::Unnamed(..) => quote!(#ident(#(#bindings),*)),
+            Fields::Unit => quote!(#ident),
+        }
+    };
+
+    let unit = quote!(());
+
+    let (head, head_binding, rest_types, rest_bindings) = match types.split_first() {
+        Some((head, rest)) => {
+            let binding = &bindings[0];
+            (quote!(#head), quote!(#binding), rest, &bindings[1..])
+        }
+        None => (unit.clone(), unit.clone(), &[][..], &[][..]),
+    };
+
+    let (tail, tail_pattern) = nest(rest_types, rest_bindings);
+
+    Ok(quote! {
+        const _: () = {
+            impl<#(#params),*> #krate::key::Product for #ty where #ty: ::core::clone::Clone, #head: ::core::clone::Clone, #tail: ::core::clone::Clone, #(#predicates,)* {
+                type Head = #head;
+                type Tail = #tail;
+
+                #[inline]
+                #[allow(clippy::unused_unit)]
+                fn split(self) -> (Self::Head, Self::Tail) {
+                    let #constructor = self;
+                    (#head_binding, #tail_pattern)
+                }
+
+                #[inline]
+                #[allow(clippy::unused_unit)]
+                fn join(#head_binding: Self::Head, #tail_pattern: Self::Tail) -> Self {
+                    #constructor
+                }
+            }
+
+            impl<#(#params,)* V> #krate::key::Key<#ty, V> for #ty
+            where
+                #ty: ::core::clone::Clone,
+                #(#predicates,)*
+                #head: #krate::key::Key<#head, <#tail as #krate::key::Key<#tail, V>>::Storage>
+                    + #krate::key::Key<#head, <#tail as #krate::key::Key<#tail, ()>>::Storage>,
+                #tail: #krate::key::Key<#tail, V> + #krate::key::Key<#tail, ()>,
+            {
+                type Storage = #krate::storage::TupleStorage<#ty, V>;
+                type SetStorage = #krate::storage::TupleStorage<#ty, ()>;
+            }
+        };
+    })
+}
+
+/// Nest the given fields as pairs, so that `(a, b, c)` becomes `(a, (b, c))`.
+///
+/// Returns the nested type and a pattern binding each field to its binding.
+/// No fields are nested as `()`, and a single field is left as it is.
+fn nest<T>(types: &[syn::Type], bindings: &[T]) -> (TokenStream, TokenStream)
+where
+    T: quote::ToTokens,
+{
+    let (last_type, init_types) = match types.split_last() {
+        Some(split) => split,
+        None => return (quote!(()), quote!(())),
+    };
+
+    let (last_binding, init_bindings) = bindings
+        .split_last()
+        .expect("Expected a binding for every field");
+
+    let mut ty = quote!(#last_type);
+    let mut pattern = quote!(#last_binding);
+
+    for (init_type, binding) in init_types.iter().zip(init_bindings).rev() {
+        ty = quote!((#init_type, #ty));
+        pattern = quote!((#binding, #pattern));
+    }
+
+    (ty, pattern)
+}
+
+/// The generic parameters of a key, which are carried over to the types and
+/// impls generated for it.
+struct KeyGenerics {
+    /// The key itself, like `Key<'a, T>`.
+    ty: TokenStream,
+    /// The parameters as they are declared on generated types, without their
+    /// defaults.
+    params: Vec<TokenStream>,
+    /// The parameters as arguments to generated types.
+    args: Vec<TokenStream>,
+    /// The predicates of the where clause of the key.
+    predicates: Vec<TokenStream>,
+    /// Bounds that every lifetime and type parameter outlives `'this`, which
+    /// are needed by the types borrowing from the storage.
+    outlives: Vec<TokenStream>,
+}
+
+impl KeyGenerics {
+    fn new(ast: &DeriveInput) -> syn::Result<KeyGenerics> {
+        let ident = &ast.ident;
+        let (_, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+        let mut params = Vec::new();
+        let mut args = Vec::new();
+        let mut outlives = Vec::new();
+
+        for param in ast.generics.params.iter() {
+            match *param {
+                syn::GenericParam::Lifetime(ref def) => {
+                    let lifetime = &def.lifetime;
+
+                    if lifetime.ident == "this" {
+                        return Err(syn::Error::new_spanned(
+                            lifetime,
+                            "the lifetime `'this` is used by the generated storage, use another name",
+                        ));
+                    }
+
+                    params.push(quote!(#def));
+                    args.push(quote!(#lifetime));
+                    outlives.push(quote!(#lifetime: 'this));
+                }
+                syn::GenericParam::Type(ref param) => {
+                    if param.ident == "V" {
+                        return Err(syn::Error::new_spanned(
+                            &param.ident,
+                            "the type parameter `V` is used for the values of the generated storage, use another name",
+                        ));
+                    }
+
+                    let mut param = param.clone();
+                    param.eq_token = None;
+                    param.default = None;
+                    let ident = &param.ident;
+                    args.push(quote!(#ident));
+                    outlives.push(quote!(#ident: 'this));
+                    params.push(quote!(#param));
+                }
+                syn::GenericParam::Const(ref param) => {
+                    let mut param = param.clone();
+                    param.eq_token = None;
+                    param.default = None;
+                    let ident = &param.ident;
+                    args.push(quote!(#ident));
+                    params.push(quote!(#param));
+                }
+            }
+        }
+
+        let predicates = match where_clause {
+            Some(where_clause) => where_clause.predicates.iter().map(|p| quote!(#p)).collect(),
+            None => Vec::new(),
+        };
+
+        Ok(KeyGenerics {
+            ty: quote!(#ident #ty_generics),
+            params,
+            args,
+            predicates,
+            outlives,
+        })
+    }
+}
+
+/// The fields of an enum variant, which are stored as a single key.
+///
+/// A variant with one field is stored as the key of that field, while a
+/// variant with several fields is stored like the equivalent tuple key, so
+/// that `Variant(A, B, C)` uses the storage of `(A, (B, C))`.
+struct VariantKey {
+    /// The type of the key which the fields are stored as.
+    ty: TokenStream,
+    /// A pattern matching the variant, binding each of its fields.
+    pattern: TokenStream,
+    /// An expression of the key built from the bindings of `pattern`.
+    key: TokenStream,
+    /// An expression of a reference to the key, built from the bindings of
+    /// `pattern` when it matches a reference to the variant.
+    key_ref: TokenStream,
+    /// The variant constructed from the fields bound by `key`.
+    constructor: TokenStream,
+}
+
+impl VariantKey {
+    fn new(ident: &Ident, var: &Ident, fields: &Fields) -> VariantKey {
+        let mut types = Vec::new();
+        let mut bindings = Vec::new();
+        let mut members = Vec::new();
+
+        for (index, field) in fields.iter().enumerate() {
+            types.push(field.ty.clone());
+            bindings.push(Ident::new(&format!("f{}", index), Span::call_site()));
+            members.push(field.ident.clone());
+        }
+
+        let (ty, key) = nest(&types, &bindings);
+
+        // A single field is already bound by reference, while several fields
+        // have to be cloned into the tuple which they are stored as.
+        let key_ref = if bindings.len() == 1 {
+            key.clone()
+        } else {
+            let clones = bindings
+                .iter()
+                .map(|b| quote!(::core::clone::Clone::clone(#b)))
+                .collect::<Vec<_>>();
+            let (_, clones) = nest(&types, &clones);
+            quote!(&#clones)
+        };
+
+        let constructor = {
+            let bindings = &bindings;
+
+            match *fields {
+                Fields::Named(..) => quote!(#ident::#var { #(#members: #bindings),* }),
+                _ => quote!(#ident::#var(#(#bindings),*)),
+            }
+        };
+
+        VariantKey {
+            ty,
+            pattern: constructor.clone(),
+            key,
+            key_ref,
+            constructor,
+        }
+    }
+
+    /// Construct the variant from the given key expression.
+    fn build(&self, k: TokenStream) -> TokenStream {
+        let key = &self.key;
+        let constructor = &self.constructor;
+        quote!({ let #key = #k; #constructor })
     }
 }
 
 /// Implement `Key` for enums.
-fn impl_storage_enum(ast: &DeriveInput, en: &DataEnum) -> TokenStream {
+fn impl_storage_enum(ast: &DeriveInput, en: &DataEnum, attrs: &Attrs) -> syn::Result<TokenStream> {
     let vis = &ast.vis;
     let ident = &ast.ident;
+    let krate = &attrs.krate;
+    let generics = KeyGenerics::new(ast)?;
+    let ty = &generics.ty;
+    let params = &generics.params;
+    let args = &generics.args;
+    let outlives = &generics.outlives;
+
+    // The generated storage matches on the key, which can't be done
+    // exhaustively by reference for an enum without variants.
+    if en.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "`Key` can't be derived for enums without variants",
+        ));
+    }
 
-    let const_wrapper = Ident::new(&format!("__IMPL_KEY_FOR_{}", ast.ident), Span::call_site());
+    let (set_storage, set_storage_impl) = if attrs.bitset.is_some() {
+        (quote!(SetStorage), impl_bitset_set_storage(ast, en, krate)?)
+    } else {
+        (quote!(Storage<#(#args,)* ()>), quote!())
+    };
+
+    let enumerable_impl = impl_enumerable(ast, en, &generics, krate);
+    let total_storage_impl = impl_total_storage(ast, en, &generics, krate);
+
+    let mut bounds = generics.predicates.clone();
+    let mut clone_bounds = Vec::new();
+    let mut partial_eq_bounds = Vec::new();
+    let mut eq_bounds = Vec::new();
 
     let mut pattern = Vec::new();
 
@@ -88,11 +750,15 @@ fn impl_storage_enum(ast: &DeriveInput, en: &DataEnum) -> TokenStream {
     let mut field_clones = Vec::new();
     let mut field_partial_eqs = Vec::new();
 
+    let mut len = Vec::new();
+    let mut is_empty = Vec::new();
     let mut get = Vec::new();
     let mut get_mut = Vec::new();
     let mut insert = Vec::new();
+    let mut try_insert = Vec::new();
     let mut remove = Vec::new();
     let mut clear = Vec::new();
+    let mut retain = Vec::new();
 
     let mut iter_clone = Vec::new();
 
@@ -102,15 +768,36 @@ fn impl_storage_enum(ast: &DeriveInput, en: &DataEnum) -> TokenStream {
     let mut iter_mut_init = Vec::new();
     let mut iter_mut_fields = Vec::new();
 
+    let mut into_iter_init = Vec::new();
+    let mut into_iter_fields = Vec::new();
+
     let mut iter_next = Vec::new();
 
+    let mut entry_variants = Vec::new();
+    let mut occupied_fields = Vec::new();
+    let mut vacant_fields = Vec::new();
+    let mut entry = Vec::new();
+    let mut occupied_key = Vec::new();
+    let mut occupied_get = Vec::new();
+    let mut occupied_get_mut = Vec::new();
+    let mut occupied_into_mut = Vec::new();
+    let mut occupied_insert = Vec::new();
+    let mut occupied_remove = Vec::new();
+    let mut vacant_key = Vec::new();
+    let mut vacant_insert = Vec::new();
+    let mut vacant_try_insert = Vec::new();
+
     for (index, variant) in en.variants.iter().enumerate() {
         let var = &variant.ident;
         let field = Ident::new(&format!("f{}", index), Span::call_site());
+        let entry_variant = Ident::new(&format!("F{}", index), Span::call_site());
+        let inline = parse_variant_attrs(variant)?;
+
+        entry_variants.push(entry_variant.clone());
 
         iter_clone.push(quote!(#field: self.#field.clone()));
 
-        field_inits.push(quote!(#field: Default::default()));
+        field_inits.push(quote!(#field: ::core::default::Default::default()));
         field_clones.push(quote!(#field: self.#field.clone()));
         field_partial_eqs.push(quote! {
             if self.#field != other.#field {
@@ -120,130 +807,269 @@ fn impl_storage_enum(ast: &DeriveInput, en: &DataEnum) -> TokenStream {
 
         match variant.fields {
             Fields::Unit => {
-                fields.push(quote!(#field: Option<V>));
+                fields.push(quote!(#field: ::core::option::Option<V>));
                 pattern.push(quote!(#ident::#var));
-                clear.push(quote!(self.#field = None));
+                clear.push(quote!(self.#field = ::core::option::Option::None));
+                retain.push(quote! {
+                    if let ::core::option::Option::Some(val) = self.#field.as_mut() {
+                        if !func(#ident::#var, val) {
+                            self.#field = ::core::option::Option::None;
+                        }
+                    }
+                });
 
+                len.push(
+                    quote!(<usize as ::core::convert::From<bool>>::from(self.#field.is_some())),
+                );
+                is_empty.push(quote!(self.#field.is_none()));
                 get.push(quote!(self.#field.as_ref()));
                 get_mut.push(quote!(self.#field.as_mut()));
-                insert.push(quote!(::std::mem::replace(&mut self.#field, Some(value))));
-                remove.push(quote!(::std::mem::replace(&mut self.#field, None)));
+                insert.push(quote!(self.#field.replace(value)));
+                try_insert.push(quote!(::core::result::Result::Ok(self.#field.replace(value))));
+                remove.push(quote!(self.#field.take()));
 
-                iter_fields.push(quote!(#field: Option<*const V>));
-                iter_init.push(quote!(#field: self.#field.as_ref().map(|v| v as *const V)));
-                iter_mut_fields.push(quote!(#field: Option<*mut V>));
-                iter_mut_init.push(quote!(#field: self.#field.as_mut().map(|v| v as *mut V)));
+                iter_fields.push(quote!(#field: ::core::option::Option<&'this V>));
+                iter_init.push(quote!(#field: self.#field.as_ref()));
+                iter_mut_fields.push(quote!(#field: ::core::option::Option<&'this mut V>));
+                iter_mut_init.push(quote!(#field: self.#field.as_mut()));
+                into_iter_fields.push(quote!(#field: ::core::option::Option<V>));
+                into_iter_init.push(quote!(#field: self.#field));
 
                 iter_next.push(quote!{
                     #index => {
-                        if let Some(v) = self.#field.take() {
-                            return Some((#ident::#var, v));
+                        if let ::core::option::Option::Some(v) = self.#field.take() {
+                            return ::core::option::Option::Some((#ident::#var, v));
                         }
 
                         self.step += 1;
                     }
                 });
+
+                occupied_fields.push(quote!(#krate::option_bucket::SomeBucket<'this, V>));
+                vacant_fields.push(quote!(#krate::option_bucket::NoneBucket<'this, V>));
+
+                entry.push(quote! {
+                    match #krate::option_bucket::OptionBucket::new(&mut self.#field) {
+                        #krate::option_bucket::OptionBucket::Some(bucket) => {
+                            #krate::map::Entry::Occupied(Occupied::#entry_variant(bucket))
+                        }
+                        #krate::option_bucket::OptionBucket::None(bucket) => {
+                            #krate::map::Entry::Vacant(Vacant::#entry_variant(bucket))
+                        }
+                    }
+                });
+
+                occupied_key.push(quote!(Occupied::#entry_variant(_) => #ident::#var));
+                occupied_get.push(quote!(Occupied::#entry_variant(bucket) => bucket.get()));
+                occupied_get_mut.push(quote!(Occupied::#entry_variant(bucket) => bucket.get_mut()));
+                occupied_into_mut
+                    .push(quote!(Occupied::#entry_variant(bucket) => bucket.into_mut()));
+                occupied_insert
+                    .push(quote!(Occupied::#entry_variant(bucket) => bucket.replace(value)));
+                occupied_remove.push(quote!(Occupied::#entry_variant(bucket) => bucket.take()));
+                vacant_key.push(quote!(Vacant::#entry_variant(_) => #ident::#var));
+                vacant_insert.push(quote!(Vacant::#entry_variant(bucket) => bucket.insert(value)));
+                vacant_try_insert.push(quote! {
+                    Vacant::#entry_variant(bucket) => ::core::result::Result::Ok(bucket.insert(value))
+                });
             },
-            Fields::Unnamed(ref unnamed) => {
-                if unnamed.unnamed.len() > 1 {
-                    panic!("Unnamed variants must have exactly one element");
-                }
+            _ => {
+                let variant_key = VariantKey::new(ident, var, &variant.fields);
+                let element = &variant_key.ty;
+                let key = &variant_key.key;
+                let key_ref = &variant_key.key_ref;
+                let storage = match inline {
+                    Some(capacity) => {
+                        // Only literals and paths can be passed as a const
+                        // argument without being wrapped in braces.
+                        let capacity = match capacity {
+                            syn::Expr::Lit(..) | syn::Expr::Path(..) => quote!(#capacity),
+                            _ => quote!({ #capacity }),
+                        };
+
+                        bounds.push(quote!(#element: ::core::clone::Clone + ::core::cmp::Eq + ::core::hash::Hash));
+                        quote!(#krate::storage::InlineMapStorage<#element, V, #capacity>)
+                    }
+                    None => {
+                        bounds.push(quote!(#element: #krate::key::Key<#element, V> + #krate::key::Key<#element, ()>));
+                        quote!(<#element as #krate::key::Key<#element, V>>::Storage)
+                    }
+                };
 
-                let element = unnamed.unnamed.first().expect("Expected one element");
-                let storage = quote!(<#element as fixed_map::key::Key<#element, V>>::Storage);
-                let as_storage = quote!(<#storage as fixed_map::storage::Storage<#element, V>>);
+                let as_storage = quote!(<#storage as #krate::storage::Storage<#element, V>>);
 
+                let build_k = variant_key.build(quote!(k));
+
+                clone_bounds.push(quote!(#storage: ::core::clone::Clone));
+                partial_eq_bounds.push(quote!(#storage: ::core::cmp::PartialEq));
+                eq_bounds.push(quote!(#storage: ::core::cmp::Eq));
                 fields.push(quote!(#field: #storage));
-                pattern.push(quote!(#ident::#var(v)));
+                pattern.push(variant_key.pattern.clone());
+
                 clear.push(quote!(self.#field.clear()));
+                retain.push(quote!(self.#field.retain(|k, v| func(#build_k, v))));
 
-                get.push(quote!(self.#field.get(v)));
-                get_mut.push(quote!(self.#field.get_mut(v)));
-                insert.push(quote!(self.#field.insert(v, value)));
-                remove.push(quote!(self.#field.remove(v)));
+                len.push(quote!(self.#field.len()));
+                is_empty.push(quote!(self.#field.is_empty()));
+                get.push(quote!(self.#field.get(#key_ref)));
+                get_mut.push(quote!(self.#field.get_mut(#key_ref)));
+                insert.push(quote!(self.#field.insert(#key, value)));
+                try_insert.push(quote! {
+                    self.#field.try_insert(#key, value).map_err(|error| {
+                        let (k, value) = error.into_inner();
+                        #krate::map::CapacityError::new(#build_k, value)
+                    })
+                });
+                remove.push(quote!(self.#field.remove(#key_ref)));
 
-                iter_fields.push(quote!(#field: #as_storage::Iter));
+                iter_fields.push(quote!(#field: #as_storage::Iter<'this>));
                 iter_init.push(quote!(#field: self.#field.iter()));
-                iter_mut_fields.push(quote!(#field: #as_storage::IterMut));
+                iter_mut_fields.push(quote!(#field: #as_storage::IterMut<'this>));
                 iter_mut_init.push(quote!(#field: self.#field.iter_mut()));
+                into_iter_fields.push(quote!(#field: #as_storage::IntoIter));
+                into_iter_init.push(quote!(#field: self.#field.into_iter()));
 
                 iter_next.push(quote!{
                     #index => {
-                        if let Some((k, v)) = self.#field.next() {
-                            return Some((#ident::#var(k), v));
+                        if let ::core::option::Option::Some((k, v)) = self.#field.next() {
+                            return ::core::option::Option::Some((#build_k, v));
                         }
 
                         self.step += 1;
                     }
                 });
+
+                occupied_fields.push(quote!(#as_storage::Occupied<'this>));
+                vacant_fields.push(quote!(#as_storage::Vacant<'this>));
+
+                entry.push(quote! {
+                    match self.#field.entry(#key) {
+                        #krate::map::Entry::Occupied(entry) => {
+                            #krate::map::Entry::Occupied(Occupied::#entry_variant(entry))
+                        }
+                        #krate::map::Entry::Vacant(entry) => {
+                            #krate::map::Entry::Vacant(Vacant::#entry_variant(entry))
+                        }
+                    }
+                });
+
+                let occupied = quote!(#krate::map::OccupiedEntry);
+                let vacant = quote!(#krate::map::VacantEntry);
+                let occupied_build = variant_key.build(quote!(#occupied::key(entry)));
+                let vacant_build = variant_key.build(quote!(#vacant::key(entry)));
+
+                occupied_key.push(quote!(Occupied::#entry_variant(entry) => #occupied_build));
+                occupied_get.push(quote!(Occupied::#entry_variant(entry) => #occupied::get(entry)));
+                occupied_get_mut
+                    .push(quote!(Occupied::#entry_variant(entry) => #occupied::get_mut(entry)));
+                occupied_into_mut
+                    .push(quote!(Occupied::#entry_variant(entry) => #occupied::into_mut(entry)));
+                occupied_insert.push(
+                    quote!(Occupied::#entry_variant(entry) => #occupied::insert(entry, value)),
+                );
+                occupied_remove
+                    .push(quote!(Occupied::#entry_variant(entry) => #occupied::remove(entry)));
+                vacant_key.push(quote!(Vacant::#entry_variant(entry) => #vacant_build));
+                vacant_insert
+                    .push(quote!(Vacant::#entry_variant(entry) => #vacant::insert(entry, value)));
+                vacant_try_insert.push(quote! {
+                    Vacant::#entry_variant(entry) => #vacant::try_insert(entry, value).map_err(|error| {
+                        let (k, value) = error.into_inner();
+                        #krate::map::CapacityError::new(#build_k, value)
+                    })
+                });
             },
-            _ => panic!("Only unit fields are supported in fixed enums"),
         }
     }
 
+    let bounds = &bounds;
     let pattern = &pattern;
     let iter_next = &iter_next;
     let iter_mut_next = iter_next;
+    let into_iter_next = iter_next;
+    let entry_variants = &entry_variants;
 
-    quote! {
-        const #const_wrapper: () = {
-            #vis struct Storage<V> {
+    Ok(quote! {
+        const _: () = {
+            use #krate::storage::Storage as _;
+
+            #vis struct Storage<#(#params,)* V> where #(#bounds,)* {
                 #(#fields,)*
             }
 
-            impl<V> Clone for Storage<V> where V: Clone {
-                fn clone(&self) -> Storage<V> {
+            impl<#(#params,)* V> ::core::clone::Clone for Storage<#(#args,)* V> where V: ::core::clone::Clone, #(#bounds,)* #(#clone_bounds,)* {
+                fn clone(&self) -> Self {
                     Storage {
                         #(#field_clones,)*
                     }
                 }
             }
 
-            impl<V> std::cmp::PartialEq for Storage<V> where V: std::cmp::PartialEq {
-                fn eq(&self, other: &Storage<V>) -> bool {
+            impl<#(#params,)* V> ::core::cmp::PartialEq for Storage<#(#args,)* V> where V: ::core::cmp::PartialEq, #(#bounds,)* #(#partial_eq_bounds,)* {
+                fn eq(&self, other: &Self) -> bool {
                     #(#field_partial_eqs;)*
                     true
                 }
             }
 
-            impl<V> std::cmp::Eq for Storage<V> where V: std::cmp::Eq {
+            impl<#(#params,)* V> ::core::cmp::Eq for Storage<#(#args,)* V> where V: ::core::cmp::Eq, #(#bounds,)* #(#eq_bounds,)* {
             }
 
-            impl<V> Default for Storage<V> {
-                fn default() -> Storage<V> {
+            impl<#(#params,)* V> ::core::default::Default for Storage<#(#args,)* V> where #(#bounds,)* {
+                fn default() -> Self {
                     Storage {
                         #(#field_inits,)*
                     }
                 }
             }
 
-            impl<V> fixed_map::storage::Storage<#ident, V> for Storage<V> {
-                type Iter = Iter<V>;
-                type IterMut = IterMut<V>;
+            impl<#(#params,)* V> #krate::storage::Storage<#ty, V> for Storage<#(#args,)* V> where #(#bounds,)* {
+                type Iter<'this> = Iter<'this, #(#args,)* V> where Self: 'this, V: 'this;
+                type IterMut<'this> = IterMut<'this, #(#args,)* V> where Self: 'this, V: 'this;
+                type IntoIter = IntoIter<#(#args,)* V>;
+                type Occupied<'this> = Occupied<'this, #(#args,)* V> where Self: 'this;
+                type Vacant<'this> = Vacant<'this, #(#args,)* V> where Self: 'this;
+
+                #[inline]
+                fn len(&self) -> usize {
+                    0 #(+ #len)*
+                }
+
+                #[inline]
+                fn is_empty(&self) -> bool {
+                    true #(&& #is_empty)*
+                }
 
                 #[inline]
-                fn insert(&mut self, key: #ident, value: V) -> Option<V> {
+                fn insert(&mut self, key: #ty, value: V) -> ::core::option::Option<V> {
                     match key {
                         #(#pattern => #insert,)*
                     }
                 }
 
                 #[inline]
-                fn get(&self, value: #ident) -> Option<&V> {
-                    match value {
-                        #(#pattern => #get,)*
+                fn try_insert(&mut self, key: #ty, value: V) -> ::core::result::Result<::core::option::Option<V>, #krate::map::CapacityError<#ty, V>> {
+                    match key {
+                        #(#pattern => #try_insert,)*
                     }
                 }
 
                 #[inline]
-                fn get_mut(&mut self, value: #ident) -> Option<&mut V> {
+                fn get(&self, value: &#ty) -> ::core::option::Option<&V> {
                     match value {
-                        #(#pattern => #get_mut,)*
+                        #(#pattern => #get,)*
+                    }
+                }
+
+                #[inline]
+                fn get_mut(&mut self, value: &#ty) -> ::core::option::Option<&mut V> {
+                    match value {
+                        #(#pattern => #get_mut,)*
                     }
                 }
 
                 #[inline]
-                fn remove(&mut self, value: #ident) -> Option<V> {
+                fn remove(&mut self, value: &#ty) -> ::core::option::Option<V> {
                     match value {
                         #(#pattern => #remove,)*
                     }
@@ -255,7 +1081,12 @@ fn impl_storage_enum(ast: &DeriveInput, en: &DataEnum) -> TokenStream {
                 }
 
                 #[inline]
-                fn iter(&self) -> Self::Iter {
+                fn retain<F>(&mut self, mut func: F) where F: ::core::ops::FnMut(#ty, &mut V) -> bool {
+                    #(#retain;)*
+                }
+
+                #[inline]
+                fn iter(&self) -> Self::Iter<'_> {
                     Iter {
                         step: 0,
                         #(#iter_init,)*
@@ -263,25 +1094,47 @@ fn impl_storage_enum(ast: &DeriveInput, en: &DataEnum) -> TokenStream {
                 }
 
                 #[inline]
-                fn iter_mut(&mut self) -> Self::IterMut {
+                fn iter_mut(&mut self) -> Self::IterMut<'_> {
                     IterMut {
                         step: 0,
                         #(#iter_mut_init,)*
                     }
                 }
+
+                #[inline]
+                fn into_iter(self) -> Self::IntoIter {
+                    IntoIter {
+                        step: 0,
+                        #(#into_iter_init,)*
+                    }
+                }
+
+                #[inline]
+                fn entry(&mut self, key: #ty) -> #krate::map::Entry<'_, Self, #ty, V> {
+                    match key {
+                        #(#pattern => #entry,)*
+                    }
+                }
             }
 
-            impl<V> fixed_map::key::Key<#ident, V> for #ident {
-                type Storage = Storage<V>;
+            impl<#(#params,)* V> #krate::key::Key<#ty, V> for #ty where #ty: ::core::clone::Clone, #(#bounds,)* {
+                type Storage = Storage<#(#args,)* V>;
+                type SetStorage = #set_storage;
             }
 
-            #vis struct Iter<V> {
+            #set_storage_impl
+
+            #enumerable_impl
+
+            #total_storage_impl
+
+            #vis struct Iter<'this, #(#params,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
                 step: usize,
                 #(#iter_fields,)*
             }
 
-            impl<V> Clone for Iter<V> {
-                fn clone(&self) -> Iter<V> {
+            impl<'this, #(#params,)* V> ::core::clone::Clone for Iter<'this, #(#args,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+                fn clone(&self) -> Self {
                     Iter {
                         step: self.step,
                         #(#iter_clone,)*
@@ -289,38 +1142,631 @@ fn impl_storage_enum(ast: &DeriveInput, en: &DataEnum) -> TokenStream {
                 }
             }
 
-            impl<V> Iterator for Iter<V> {
-                type Item = (#ident, *const V);
+            impl<'this, #(#params,)* V> ::core::iter::Iterator for Iter<'this, #(#args,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+                type Item = (#ty, &'this V);
 
                 #[inline]
-                fn next(&mut self) -> Option<Self::Item> {
+                fn next(&mut self) -> ::core::option::Option<Self::Item> {
                     loop {
                         match self.step {
                             #(#iter_next,)*
-                            _ => return None,
+                            _ => return ::core::option::Option::None,
                         }
                     }
                 }
             }
 
-            #vis struct IterMut<V> {
+            #vis struct IntoIter<#(#params,)* V> where #(#bounds,)* {
+                step: usize,
+                #(#into_iter_fields,)*
+            }
+
+            impl<#(#params,)* V> ::core::iter::Iterator for IntoIter<#(#args,)* V> where #(#bounds,)* {
+                type Item = (#ty, V);
+
+                #[inline]
+                fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                    loop {
+                        match self.step {
+                            #(#into_iter_next,)*
+                            _ => return ::core::option::Option::None,
+                        }
+                    }
+                }
+            }
+
+            #vis enum Occupied<'this, #(#params,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+                #(#entry_variants(#occupied_fields),)*
+            }
+
+            impl<'this, #(#params,)* V> #krate::map::OccupiedEntry<'this, #ty, V> for Occupied<'this, #(#args,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+                #[inline]
+                fn key(&self) -> #ty {
+                    match self {
+                        #(#occupied_key,)*
+                    }
+                }
+
+                #[inline]
+                fn get(&self) -> &V {
+                    match self {
+                        #(#occupied_get,)*
+                    }
+                }
+
+                #[inline]
+                fn get_mut(&mut self) -> &mut V {
+                    match self {
+                        #(#occupied_get_mut,)*
+                    }
+                }
+
+                #[inline]
+                fn into_mut(self) -> &'this mut V {
+                    match self {
+                        #(#occupied_into_mut,)*
+                    }
+                }
+
+                #[inline]
+                fn insert(&mut self, value: V) -> V {
+                    match self {
+                        #(#occupied_insert,)*
+                    }
+                }
+
+                #[inline]
+                fn remove(self) -> V {
+                    match self {
+                        #(#occupied_remove,)*
+                    }
+                }
+            }
+
+            #vis enum Vacant<'this, #(#params,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+                #(#entry_variants(#vacant_fields),)*
+            }
+
+            impl<'this, #(#params,)* V> #krate::map::VacantEntry<'this, #ty, V> for Vacant<'this, #(#args,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+                #[inline]
+                fn key(&self) -> #ty {
+                    match self {
+                        #(#vacant_key,)*
+                    }
+                }
+
+                #[inline]
+                fn insert(self, value: V) -> &'this mut V {
+                    match self {
+                        #(#vacant_insert,)*
+                    }
+                }
+
+                #[inline]
+                fn try_insert(self, value: V) -> ::core::result::Result<&'this mut V, #krate::map::CapacityError<#ty, V>> {
+                    match self {
+                        #(#vacant_try_insert,)*
+                    }
+                }
+            }
+
+            #vis struct IterMut<'this, #(#params,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
                 step: usize,
                 #(#iter_mut_fields,)*
             }
 
-            impl<V> Iterator for IterMut<V> {
-                type Item = (#ident, *mut V);
+            impl<'this, #(#params,)* V> ::core::iter::Iterator for IterMut<'this, #(#args,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+                type Item = (#ty, &'this mut V);
 
                 #[inline]
-                fn next(&mut self) -> Option<Self::Item> {
+                fn next(&mut self) -> ::core::option::Option<Self::Item> {
                     loop {
                         match self.step {
                             #(#iter_mut_next,)*
-                            _ => return None,
+                            _ => return ::core::option::Option::None,
                         }
                     }
                 }
             }
         };
+    })
+}
+
+/// Implement `Enumerable` for enums.
+///
+/// Every variant with a field adds a bound that the type of its field is
+/// `Enumerable`. These bounds are wrapped in a `for<'enumerable>` so that they
+/// are only checked when the implementation is used, which keeps the derive
+/// working for keys which cannot be enumerated, like `Key::Number(u32)`. The
+/// same goes for the `Copy` bound which `Enumerable` requires of the key
+/// itself.
+fn impl_enumerable(
+    ast: &DeriveInput,
+    en: &DataEnum,
+    generics: &KeyGenerics,
+    krate: &TokenStream,
+) -> TokenStream {
+    let ident = &ast.ident;
+    let ty = &generics.ty;
+    let params = &generics.params;
+
+    let mut bounds = generics.predicates.clone();
+    let mut ordinal = Vec::new();
+    let mut from_ordinal = Vec::new();
+    let mut count = vec![quote!(0)];
+
+    for variant in en.variants.iter() {
+        let var = &variant.ident;
+        let offset = {
+            let count = &count;
+            quote!((#(#count)+*))
+        };
+
+        match variant.fields {
+            Fields::Unit => {
+                ordinal.push(quote!(#ident::#var => #offset));
+                from_ordinal.push(quote! {
+                    if ordinal < 1 {
+                        return ::core::option::Option::Some(#ident::#var);
+                    }
+
+                    ordinal -= 1;
+                });
+                count.push(quote!(1));
+            }
+            _ => {
+                let variant_key = VariantKey::new(ident, var, &variant.fields);
+                let element = &variant_key.ty;
+                let pattern = &variant_key.pattern;
+                let key = &variant_key.key;
+                let build_k = variant_key.build(quote!(k));
+                let as_enumerable = quote!(<#element as #krate::key::Enumerable>);
+
+                bounds.push(quote!(for<'enumerable> #element: #krate::key::Enumerable));
+                ordinal.push(quote!(#pattern => #offset + #as_enumerable::ordinal(#key)));
+                from_ordinal.push(quote! {
+                    if ordinal < #as_enumerable::COUNT {
+                        return #as_enumerable::from_ordinal(ordinal).map(|k| #build_k);
+                    }
+
+                    ordinal -= #as_enumerable::COUNT;
+                });
+                count.push(quote!(#as_enumerable::COUNT));
+            }
+        }
+    }
+
+    let count = &count;
+
+    quote! {
+        impl<#(#params),*> #krate::key::Enumerable for #ty where for<'enumerable> #ty: ::core::marker::Copy, #(#bounds,)* {
+            const COUNT: usize = #(#count)+*;
+
+            #[inline]
+            fn ordinal(self) -> usize {
+                match self {
+                    #(#ordinal,)*
+                }
+            }
+
+            #[inline]
+            fn from_ordinal(mut ordinal: usize) -> ::core::option::Option<Self> {
+                #(#from_ordinal)*
+                let _ = ordinal;
+                ::core::option::Option::None
+            }
+        }
+    }
+}
+
+/// Implement `TotalKey` for enums, with a storage that holds a value for
+/// every key.
+///
+/// Like `Enumerable`, the bounds on the fields of variants are wrapped in a
+/// `for<'total>` so that keys which can't have a total storage still derive.
+fn impl_total_storage(
+    ast: &DeriveInput,
+    en: &DataEnum,
+    generics: &KeyGenerics,
+    krate: &TokenStream,
+) -> TokenStream {
+    let vis = &ast.vis;
+    let ident = &ast.ident;
+    let ty = &generics.ty;
+    let params = &generics.params;
+    let args = &generics.args;
+    let outlives = &generics.outlives;
+
+    let mut bounds = generics.predicates.clone();
+    let mut pattern = Vec::new();
+
+    let mut fields = Vec::new();
+    let mut try_from_fn = Vec::new();
+    let mut get = Vec::new();
+    let mut get_mut = Vec::new();
+
+    let mut iter_clone = Vec::new();
+    let mut iter_fields = Vec::new();
+    let mut iter_init = Vec::new();
+    let mut iter_mut_fields = Vec::new();
+    let mut iter_mut_init = Vec::new();
+    let mut into_iter_fields = Vec::new();
+    let mut into_iter_init = Vec::new();
+    let mut iter_next = Vec::new();
+
+    for (index, variant) in en.variants.iter().enumerate() {
+        let var = &variant.ident;
+        let field = Ident::new(&format!("f{}", index), Span::call_site());
+
+        iter_clone.push(quote!(#field: self.#field.clone()));
+
+        match variant.fields {
+            Fields::Unit => {
+                fields.push(quote!(#field: V));
+                pattern.push(quote!(#ident::#var));
+                try_from_fn.push(quote!(#field: func(#ident::#var)?));
+                get.push(quote!(&self.#field));
+                get_mut.push(quote!(&mut self.#field));
+
+                iter_fields.push(quote!(#field: ::core::option::Option<&'this V>));
+                iter_init.push(quote!(#field: ::core::option::Option::Some(&self.#field)));
+                iter_mut_fields.push(quote!(#field: ::core::option::Option<&'this mut V>));
+                iter_mut_init.push(quote!(#field: ::core::option::Option::Some(&mut self.#field)));
+                into_iter_fields.push(quote!(#field: ::core::option::Option<V>));
+                into_iter_init.push(quote!(#field: ::core::option::Option::Some(self.#field)));
+
+                iter_next.push(quote! {
+                    #index => {
+                        if let ::core::option::Option::Some(v) = self.#field.take() {
+                            return ::core::option::Option::Some((#ident::#var, v));
+                        }
+
+                        self.step += 1;
+                    }
+                });
+            }
+            _ => {
+                let variant_key = VariantKey::new(ident, var, &variant.fields);
+                let element = &variant_key.ty;
+                let key = &variant_key.key;
+                let build_k = variant_key.build(quote!(k));
+                let storage =
+                    quote!(<#element as #krate::key::TotalKey<#element, V>>::TotalStorage);
+                let as_storage = quote!(<#storage as #krate::storage::TotalStorage<#element, V>>);
+
+                bounds.push(quote!(for<'total> #element: #krate::key::TotalKey<#element, V>));
+                fields.push(quote!(#field: #storage));
+                pattern.push(variant_key.pattern.clone());
+                try_from_fn.push(quote!(#field: #as_storage::try_from_fn(|k| func(#build_k))?));
+                get.push(quote!(#as_storage::get(&self.#field, #key)));
+                get_mut.push(quote!(#as_storage::get_mut(&mut self.#field, #key)));
+
+                iter_fields.push(quote!(#field: #as_storage::Iter<'this>));
+                iter_init.push(quote!(#field: #as_storage::iter(&self.#field)));
+                iter_mut_fields.push(quote!(#field: #as_storage::IterMut<'this>));
+                iter_mut_init.push(quote!(#field: #as_storage::iter_mut(&mut self.#field)));
+                into_iter_fields.push(quote!(#field: #as_storage::IntoIter));
+                into_iter_init.push(quote!(#field: #as_storage::into_iter(self.#field)));
+
+                iter_next.push(quote! {
+                    #index => {
+                        if let ::core::option::Option::Some((k, v)) = self.#field.next() {
+                            return ::core::option::Option::Some((#build_k, v));
+                        }
+
+                        self.step += 1;
+                    }
+                });
+            }
+        }
     }
+
+    let bounds = &bounds;
+    let pattern = &pattern;
+    let iter_next = &iter_next;
+    let iter_mut_next = iter_next;
+    let into_iter_next = iter_next;
+
+    quote! {
+        #vis struct TotalStorage<#(#params,)* V> where #(#bounds,)* {
+            #(#fields,)*
+        }
+
+        impl<#(#params,)* V> #krate::storage::TotalStorage<#ty, V> for TotalStorage<#(#args,)* V> where #(#bounds,)* {
+            type Iter<'this> = TotalIter<'this, #(#args,)* V> where Self: 'this, V: 'this;
+            type IterMut<'this> = TotalIterMut<'this, #(#args,)* V> where Self: 'this, V: 'this;
+            type IntoIter = TotalIntoIter<#(#args,)* V>;
+
+            #[inline]
+            fn try_from_fn<F, E>(mut func: F) -> ::core::result::Result<Self, E> where F: ::core::ops::FnMut(#ty) -> ::core::result::Result<V, E> {
+                ::core::result::Result::Ok(TotalStorage {
+                    #(#try_from_fn,)*
+                })
+            }
+
+            #[inline]
+            fn get(&self, key: #ty) -> &V {
+                match key {
+                    #(#pattern => #get,)*
+                }
+            }
+
+            #[inline]
+            fn get_mut(&mut self, key: #ty) -> &mut V {
+                match key {
+                    #(#pattern => #get_mut,)*
+                }
+            }
+
+            #[inline]
+            fn iter(&self) -> Self::Iter<'_> {
+                TotalIter {
+                    step: 0,
+                    #(#iter_init,)*
+                }
+            }
+
+            #[inline]
+            fn iter_mut(&mut self) -> Self::IterMut<'_> {
+                TotalIterMut {
+                    step: 0,
+                    #(#iter_mut_init,)*
+                }
+            }
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                TotalIntoIter {
+                    step: 0,
+                    #(#into_iter_init,)*
+                }
+            }
+        }
+
+        impl<#(#params,)* V> #krate::key::TotalKey<#ty, V> for #ty where for<'total> #ty: ::core::marker::Copy, #(#bounds,)* {
+            type TotalStorage = TotalStorage<#(#args,)* V>;
+        }
+
+        #vis struct TotalIter<'this, #(#params,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+            step: usize,
+            #(#iter_fields,)*
+        }
+
+        impl<'this, #(#params,)* V> ::core::clone::Clone for TotalIter<'this, #(#args,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+            fn clone(&self) -> Self {
+                TotalIter {
+                    step: self.step,
+                    #(#iter_clone,)*
+                }
+            }
+        }
+
+        impl<'this, #(#params,)* V> ::core::iter::Iterator for TotalIter<'this, #(#args,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+            type Item = (#ty, &'this V);
+
+            #[inline]
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                loop {
+                    match self.step {
+                        #(#iter_next,)*
+                        _ => return ::core::option::Option::None,
+                    }
+                }
+            }
+        }
+
+        #vis struct TotalIterMut<'this, #(#params,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+            step: usize,
+            #(#iter_mut_fields,)*
+        }
+
+        impl<'this, #(#params,)* V> ::core::iter::Iterator for TotalIterMut<'this, #(#args,)* V> where V: 'this, #(#outlives,)* #(#bounds,)* {
+            type Item = (#ty, &'this mut V);
+
+            #[inline]
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                loop {
+                    match self.step {
+                        #(#iter_mut_next,)*
+                        _ => return ::core::option::Option::None,
+                    }
+                }
+            }
+        }
+
+        #vis struct TotalIntoIter<#(#params,)* V> where #(#bounds,)* {
+            step: usize,
+            #(#into_iter_fields,)*
+        }
+
+        impl<#(#params,)* V> ::core::iter::Iterator for TotalIntoIter<#(#args,)* V> where #(#bounds,)* {
+            type Item = (#ty, V);
+
+            #[inline]
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                loop {
+                    match self.step {
+                        #(#into_iter_next,)*
+                        _ => return ::core::option::Option::None,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Implement a set storage for enums with unit variants, where each variant
+/// is represented by a single bit.
+fn impl_bitset_set_storage(
+    ast: &DeriveInput,
+    en: &DataEnum,
+    krate: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let vis = &ast.vis;
+    let ident = &ast.ident;
+
+    let mut pattern = Vec::new();
+    let mut index = Vec::new();
+
+    for (n, variant) in en.variants.iter().enumerate() {
+        let var = &variant.ident;
+
+        match variant.fields {
+            Fields::Unit => (),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &variant.fields,
+                    "`#[key(bitset)]` is only supported on enums with unit variants",
+                ));
+            }
+        }
+
+        pattern.push(quote!(#ident::#var));
+        index.push(n as u32);
+    }
+
+    let bits = match pattern.len() {
+        0..=8 => quote!(u8),
+        9..=16 => quote!(u16),
+        17..=32 => quote!(u32),
+        33..=64 => quote!(u64),
+        65..=128 => quote!(u128),
+        n => {
+            let words = (n + 63) / 64;
+            quote!([u64; #words])
+        }
+    };
+
+    let pattern = &pattern;
+    let index = &index;
+
+    Ok(quote! {
+        #vis struct SetStorage {
+            bits: #bits,
+        }
+
+        impl SetStorage {
+            #[inline]
+            fn index(key: &#ident) -> u32 {
+                match key {
+                    #(#pattern => #index,)*
+                }
+            }
+
+            #[inline]
+            fn key(index: u32) -> #ident {
+                match index {
+                    #(#index => #pattern,)*
+                    _ => ::core::unreachable!("index out of bounds for bitset"),
+                }
+            }
+        }
+
+        impl ::core::clone::Clone for SetStorage {
+            fn clone(&self) -> SetStorage {
+                SetStorage {
+                    bits: self.bits,
+                }
+            }
+        }
+
+        impl ::core::cmp::PartialEq for SetStorage {
+            fn eq(&self, other: &SetStorage) -> bool {
+                self.bits == other.bits
+            }
+        }
+
+        impl ::core::cmp::Eq for SetStorage {
+        }
+
+        impl ::core::default::Default for SetStorage {
+            fn default() -> SetStorage {
+                SetStorage {
+                    bits: #krate::storage::Bits::EMPTY,
+                }
+            }
+        }
+
+        impl #krate::storage::SetStorage<#ident> for SetStorage {
+            type Iter<'this> = SetIter where Self: 'this;
+            type IntoIter = SetIter;
+
+            #[inline]
+            fn len(&self) -> usize {
+                #krate::storage::Bits::count(&self.bits)
+            }
+
+            #[inline]
+            fn is_empty(&self) -> bool {
+                #krate::storage::Bits::is_empty(&self.bits)
+            }
+
+            #[inline]
+            fn insert(&mut self, key: #ident) -> bool {
+                #krate::storage::Bits::set(&mut self.bits, SetStorage::index(&key))
+            }
+
+            #[inline]
+            fn contains(&self, key: &#ident) -> bool {
+                #krate::storage::Bits::test(&self.bits, SetStorage::index(key))
+            }
+
+            #[inline]
+            fn remove(&mut self, key: &#ident) -> bool {
+                #krate::storage::Bits::unset(&mut self.bits, SetStorage::index(key))
+            }
+
+            #[inline]
+            fn retain<F>(&mut self, mut func: F) where F: ::core::ops::FnMut(#ident) -> bool {
+                let mut bits = self.bits;
+
+                while let ::core::option::Option::Some(index) = #krate::storage::Bits::pop_lowest(&mut bits) {
+                    if !func(SetStorage::key(index)) {
+                        #krate::storage::Bits::unset(&mut self.bits, index);
+                    }
+                }
+            }
+
+            #[inline]
+            fn clear(&mut self) {
+                self.bits = #krate::storage::Bits::EMPTY;
+            }
+
+            #[inline]
+            fn iter(&self) -> Self::Iter<'_> {
+                SetIter {
+                    bits: self.bits,
+                }
+            }
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                SetIter {
+                    bits: self.bits,
+                }
+            }
+        }
+
+        #vis struct SetIter {
+            bits: #bits,
+        }
+
+        impl ::core::clone::Clone for SetIter {
+            fn clone(&self) -> SetIter {
+                SetIter {
+                    bits: self.bits,
+                }
+            }
+        }
+
+        impl ::core::iter::Iterator for SetIter {
+            type Item = #ident;
+
+            #[inline]
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let index = #krate::storage::Bits::pop_lowest(&mut self.bits)?;
+                ::core::option::Option::Some(SetStorage::key(index))
+            }
+        }
+    })
 }
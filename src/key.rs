@@ -1,28 +1,177 @@
 //! Module for the trait to define a `Key`.
 
-use crate::storage::{BooleanStorage, MapStorage, OptionStorage, SingletonStorage, Storage};
+use crate::storage::{
+    BooleanStorage, BooleanTotalStorage, ByteStorage, OptionStorage, OptionTotalStorage,
+    OrderingStorage, ResultStorage, SetStorage, SingletonStorage, SingletonTotalStorage, Storage,
+    TotalStorage, TupleStorage,
+};
+use core::cmp::Ordering;
+use core::iter;
+use core::marker;
+
+#[cfg(feature = "alloc")]
+use crate::storage::{DenseStorage, MapStorage};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::hash;
+#[cfg(feature = "alloc")]
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
 
 /// The trait for a key that can be used to store values in the maps.
-pub trait Key<K, V>: Copy {
+///
+/// Keys only have to be `Clone`, so owned keys like `String` can be used,
+/// either directly or as the field of a derived key. Maps and sets look up
+/// keys by reference, so looking up a key like `String`, or a variant with a
+/// single field, doesn't clone it. Keys which are made up of several keys,
+/// like tuples, arrays, structs and variants with several fields, are split
+/// into their parts by value, so their parts are cloned to look them up.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, PartialEq, Eq, Key)]
+/// enum Ident {
+///     SelfValue,
+///     Custom(String),
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Ident::SelfValue, 1);
+/// map.insert(Ident::Custom(String::from("foo")), 2);
+///
+/// let foo = Ident::Custom(String::from("foo"));
+/// assert_eq!(map.get(&Ident::SelfValue), Some(&1));
+/// assert_eq!(map.get(&foo), Some(&2));
+/// # }
+/// ```
+pub trait Key<K, V>: Clone {
     /// The `Storage` implementation to use for the key implementing this trait.
     type Storage: Storage<K, V>;
+
+    /// The `SetStorage` implementation to use when the key implementing this
+    /// trait is stored in a [`Set`](../struct.Set.html).
+    ///
+    /// Unless the key has a more compact representation, this is its
+    /// `Storage` with `()` as the value.
+    type SetStorage: SetStorage<K>;
 }
 
+/// A key which is made up of two other keys, its head and its tail.
+///
+/// Such keys are stored in a [`TupleStorage`], which stores the tail of the key
+/// in the storage of its head. This is implemented for tuples of up to eight
+/// keys, where the head is the first key and the tail is a tuple of the
+/// remaining keys, for arrays of up to sixteen keys, where the head is the
+/// first element and the tail is an array of the remaining elements, and by
+/// the `Key` derive for structs, where the head is the first field and the
+/// tail holds the remaining fields as nested pairs.
+///
+/// [`TupleStorage`]: ../storage/struct.TupleStorage.html
+///
+/// # Examples
+///
+/// ```rust
+/// use fixed_map::{key::Product, Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Side {
+///     Left,
+///     Right,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// struct Cell {
+///     layer: u8,
+///     side: Side,
+/// }
+///
+/// let cell = Cell { layer: 1, side: Side::Right };
+/// assert_eq!(cell.split(), (1, Side::Right));
+/// assert_eq!(Cell::join(1, Side::Right), cell);
+///
+/// let mut map = Map::new();
+/// map.insert(cell, "top right");
+/// assert_eq!(map.get(cell), Some(&"top right"));
+/// ```
+pub trait Product: Clone {
+    /// The first part of the key.
+    type Head: Clone;
+
+    /// The remaining parts of the key.
+    type Tail: Clone;
+
+    /// Split the key into its head and tail.
+    fn split(self) -> (Self::Head, Self::Tail);
+
+    /// Construct the key from its head and tail.
+    fn join(head: Self::Head, tail: Self::Tail) -> Self;
+}
+
+/// The trait for a key that can be used in a [`TotalMap`], which stores a
+/// value for every possible key.
+///
+/// This is implemented by the `Key` derive for enums whose variants are either
+/// unit variants or have fields whose types are themselves `TotalKey`, and for
+/// `bool`, `()` and `Option<K>`.
+///
+/// [`TotalMap`]: ../struct.TotalMap.html
+pub trait TotalKey<K, V>: Copy {
+    /// The `TotalStorage` implementation to use for the key implementing this
+    /// trait.
+    type TotalStorage: TotalStorage<K, V>;
+}
+
+#[cfg(feature = "alloc")]
 impl<V> Key<&'static str, V> for &'static str {
     type Storage = MapStorage<Self, V>;
+    type SetStorage = MapStorage<Self, ()>;
+}
+
+#[cfg(feature = "alloc")]
+impl<V> Key<&'static [u8], V> for &'static [u8] {
+    type Storage = MapStorage<Self, V>;
+    type SetStorage = MapStorage<Self, ()>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, V> Key<Wrapping<T>, V> for Wrapping<T>
+where
+    T: Clone + Eq + hash::Hash,
+{
+    type Storage = MapStorage<Self, V>;
+    type SetStorage = MapStorage<Self, ()>;
 }
 
 impl<K, V> Key<Option<K>, V> for Option<K>
 where
-    K: Key<K, V>,
+    K: Key<K, V> + Key<K, ()>,
 {
     type Storage = OptionStorage<K, V>;
+    type SetStorage = OptionStorage<K, ()>;
+}
+
+impl<T, E, V> Key<Result<T, E>, V> for Result<T, E>
+where
+    T: Key<T, V> + Key<T, ()>,
+    E: Key<E, V> + Key<E, ()>,
+{
+    type Storage = ResultStorage<T, E, V>;
+    type SetStorage = ResultStorage<T, E, ()>;
 }
 
 macro_rules! impl_map_storage {
     ($ty:ty) => {
+        #[cfg(feature = "alloc")]
         impl<V> Key<$ty, V> for $ty {
             type Storage = MapStorage<$ty, V>;
+            type SetStorage = MapStorage<$ty, ()>;
         }
     };
 }
@@ -31,23 +180,650 @@ macro_rules! impl_singleton_storage {
     ($ty:ty) => {
         impl<V> Key<$ty, V> for $ty {
             type Storage = SingletonStorage<$ty, V>;
+            type SetStorage = SingletonStorage<$ty, ()>;
         }
     };
 }
 
 impl_map_storage!(char);
-impl_map_storage!(u8);
+impl_map_storage!(u16);
 impl_map_storage!(u32);
 impl_map_storage!(u64);
 impl_map_storage!(u128);
 impl_map_storage!(usize);
-impl_map_storage!(i8);
+impl_map_storage!(i16);
 impl_map_storage!(i32);
 impl_map_storage!(i64);
 impl_map_storage!(i128);
 impl_map_storage!(isize);
+impl_map_storage!(NonZeroU8);
+impl_map_storage!(NonZeroU16);
+impl_map_storage!(NonZeroU32);
+impl_map_storage!(NonZeroU64);
+impl_map_storage!(NonZeroU128);
+impl_map_storage!(NonZeroUsize);
+impl_map_storage!(NonZeroI8);
+impl_map_storage!(NonZeroI16);
+impl_map_storage!(NonZeroI32);
+impl_map_storage!(NonZeroI64);
+impl_map_storage!(NonZeroI128);
+impl_map_storage!(NonZeroIsize);
+impl_map_storage!(String);
+impl_map_storage!(Box<str>);
+impl_map_storage!(Vec<u8>);
 impl_singleton_storage!(());
 
+/// A key which is stored densely, with a slot for every possible value of the
+/// key.
+///
+/// `u8` and `i8` are always stored like this, inline in a [`ByteStorage`],
+/// while this wrapper allows opting into dense storage for `u16` and `i16`
+/// keys, which are otherwise stored in a hash map. These are stored in a
+/// [`DenseStorage`], which allocates a slot for each of their 65536 values on
+/// first insert, but looking them up is only an index into those slots. Since
+/// the slots are allocated, `Dense<u16>` and `Dense<i16>` require the `alloc`
+/// feature.
+///
+/// [`ByteStorage`]: ../storage/struct.ByteStorage.html
+/// [`DenseStorage`]: ../storage/struct.DenseStorage.html
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use fixed_map::{key::Dense, Map};
+///
+/// let mut map = Map::new();
+/// map.insert(Dense(0x1234u16), "handler");
+///
+/// assert_eq!(map.get(Dense(0x1234)), Some(&"handler"));
+/// assert_eq!(map.get(Dense(0x4321)), None);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dense<T>(pub T);
+
+macro_rules! impl_dense_enumerable {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Enumerable for Dense<$ty> {
+                const COUNT: usize = (<$ty>::MAX as i64 - <$ty>::MIN as i64 + 1) as usize;
+
+                #[inline]
+                fn ordinal(self) -> usize {
+                    (self.0 as i64 - <$ty>::MIN as i64) as usize
+                }
+
+                #[inline]
+                fn from_ordinal(ordinal: usize) -> Option<Self> {
+                    if ordinal < Self::COUNT {
+                        Some(Dense((ordinal as i64 + <$ty>::MIN as i64) as $ty))
+                    } else {
+                        None
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_dense_enumerable!(u8, i8, u16, i16);
+
+/// Implement `Key` for keys which are stored in the given dense storage.
+macro_rules! impl_dense_storage {
+    ($storage:ident, $($ty:ty),* $(,)?) => {
+        $(
+            impl<V> Key<$ty, V> for $ty {
+                type Storage = $storage<Self, V>;
+                type SetStorage = $storage<Self, ()>;
+            }
+        )*
+    };
+}
+
+impl_dense_storage!(ByteStorage, u8, i8, Dense<u8>, Dense<i8>);
+#[cfg(feature = "alloc")]
+impl_dense_storage!(DenseStorage, Dense<u16>, Dense<i16>);
+
+impl<A, B> Product for (A, B)
+where
+    A: Clone,
+    B: Clone,
+{
+    type Head = A;
+    type Tail = B;
+
+    #[inline]
+    fn split(self) -> (A, B) {
+        self
+    }
+
+    #[inline]
+    fn join(head: A, tail: B) -> Self {
+        (head, tail)
+    }
+}
+
+impl<A, B, V> Key<(A, B), V> for (A, B)
+where
+    A: Key<A, <B as Key<B, V>>::Storage> + Key<A, <B as Key<B, ()>>::Storage>,
+    B: Key<B, V> + Key<B, ()>,
+{
+    type Storage = TupleStorage<(A, B), V>;
+    type SetStorage = TupleStorage<(A, B), ()>;
+}
+
+/// Implement `Product`, `Key` and `Enumerable` for a tuple of three or more
+/// keys, where the head is the first key and the tail is a tuple of the
+/// remaining keys.
+macro_rules! impl_tuple {
+    ($head:ident, $($tail:ident),+) => {
+        impl<$head, $($tail),+> Product for ($head, $($tail),+)
+        where
+            $head: Clone,
+            $($tail: Clone,)+
+        {
+            type Head = $head;
+            type Tail = ($($tail,)+);
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn split(self) -> (Self::Head, Self::Tail) {
+                let ($head, $($tail),+) = self;
+                ($head, ($($tail,)+))
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn join($head: Self::Head, ($($tail,)+): Self::Tail) -> Self {
+                ($head, $($tail),+)
+            }
+        }
+
+        impl<$head, $($tail),+, V> Key<($head, $($tail),+), V> for ($head, $($tail),+)
+        where
+            $head: Key<$head, <($($tail,)+) as Key<($($tail,)+), V>>::Storage>
+                + Key<$head, <($($tail,)+) as Key<($($tail,)+), ()>>::Storage>,
+            ($($tail,)+): Key<($($tail,)+), V> + Key<($($tail,)+), ()>,
+            $($tail: Clone,)+
+        {
+            type Storage = TupleStorage<Self, V>;
+            type SetStorage = TupleStorage<Self, ()>;
+        }
+
+        impl<$head, $($tail),+> Enumerable for ($head, $($tail),+)
+        where
+            $head: Enumerable,
+            ($($tail,)+): Enumerable,
+            $($tail: Copy,)+
+        {
+            const COUNT: usize = <($head, ($($tail,)+))>::COUNT;
+
+            #[inline]
+            fn ordinal(self) -> usize {
+                Enumerable::ordinal(self.split())
+            }
+
+            #[inline]
+            fn from_ordinal(ordinal: usize) -> Option<Self> {
+                let (head, tail) = <($head, ($($tail,)+))>::from_ordinal(ordinal)?;
+                Some(Self::join(head, tail))
+            }
+        }
+    };
+}
+
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+impl_tuple!(A, B, C, D, E, F, G);
+impl_tuple!(A, B, C, D, E, F, G, H);
+
+impl<K, V> Key<[K; 0], V> for [K; 0]
+where
+    K: Clone,
+{
+    type Storage = SingletonStorage<Self, V>;
+    type SetStorage = SingletonStorage<Self, ()>;
+}
+
+impl<K> Enumerable for [K; 0]
+where
+    K: Copy,
+{
+    const COUNT: usize = 1;
+
+    #[inline]
+    fn ordinal(self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn from_ordinal(ordinal: usize) -> Option<Self> {
+        match ordinal {
+            0 => Some([]),
+            _ => None,
+        }
+    }
+}
+
+/// Implement `Product`, `Key` and `Enumerable` for arrays, where the head is
+/// the first element and the tail is an array of the remaining elements.
+macro_rules! impl_array {
+    ($($len:literal => $tail:literal),* $(,)?) => {
+        $(
+            impl<K> Product for [K; $len]
+            where
+                K: Clone,
+            {
+                type Head = K;
+                type Tail = [K; $tail];
+
+                #[inline]
+                fn split(self) -> (K, [K; $tail]) {
+                    let [head, tail @ ..] = self;
+                    (head, tail)
+                }
+
+                #[inline]
+                fn join(head: K, tail: [K; $tail]) -> Self {
+                    let mut elements = iter::once(head).chain(tail);
+
+                    core::array::from_fn(|_| match elements.next() {
+                        Some(element) => element,
+                        None => unreachable!("the tail has one element less than the key"),
+                    })
+                }
+            }
+
+            impl<K, V> Key<[K; $len], V> for [K; $len]
+            where
+                K: Key<K, <[K; $tail] as Key<[K; $tail], V>>::Storage>
+                    + Key<K, <[K; $tail] as Key<[K; $tail], ()>>::Storage>,
+                [K; $tail]: Key<[K; $tail], V> + Key<[K; $tail], ()>,
+            {
+                type Storage = TupleStorage<Self, V>;
+                type SetStorage = TupleStorage<Self, ()>;
+            }
+
+            impl<K> Enumerable for [K; $len]
+            where
+                K: Enumerable,
+            {
+                const COUNT: usize = <(K, [K; $tail])>::COUNT;
+
+                #[inline]
+                fn ordinal(self) -> usize {
+                    Enumerable::ordinal(self.split())
+                }
+
+                #[inline]
+                fn from_ordinal(ordinal: usize) -> Option<Self> {
+                    let (head, tail) = <(K, [K; $tail])>::from_ordinal(ordinal)?;
+                    Some(Self::join(head, tail))
+                }
+            }
+        )*
+    };
+}
+
+impl_array! {
+    1 => 0,
+    2 => 1,
+    3 => 2,
+    4 => 3,
+    5 => 4,
+    6 => 5,
+    7 => 6,
+    8 => 7,
+    9 => 8,
+    10 => 9,
+    11 => 10,
+    12 => 11,
+    13 => 12,
+    14 => 13,
+    15 => 14,
+    16 => 15,
+}
+
 impl<V> Key<bool, V> for bool {
     type Storage = BooleanStorage<V>;
+    type SetStorage = BooleanStorage<()>;
+}
+
+impl<V> Key<Ordering, V> for Ordering {
+    type Storage = OrderingStorage<V>;
+    type SetStorage = OrderingStorage<()>;
+}
+
+impl<V> TotalKey<(), V> for () {
+    type TotalStorage = SingletonTotalStorage<V>;
+}
+
+impl<V> TotalKey<bool, V> for bool {
+    type TotalStorage = BooleanTotalStorage<V>;
+}
+
+impl<K, V> TotalKey<Option<K>, V> for Option<K>
+where
+    K: TotalKey<K, V>,
+{
+    type TotalStorage = OptionTotalStorage<K, V>;
+}
+
+/// A key with a finite number of values, each of which has an ordinal.
+///
+/// Ordinals are assigned in the order in which a [`Map`] iterates over its
+/// keys, so they can be used to build lists and lookup tables which are laid
+/// out the same way as a map.
+///
+/// This is implemented by the `Key` derive for enums whose variants are either
+/// unit variants or have fields which are themselves `Enumerable`, and for
+/// `bool`, `()`, `Ordering`, `u8`, `i8`, [`Dense`] integers, `Option<K>`,
+/// `Result<T, E>`, tuples of up to eight `Enumerable` keys and arrays of up to
+/// sixteen `Enumerable` keys. Tuples and arrays are enumerated by their first
+/// key, and then by the remaining keys. Integers are enumerated in ascending
+/// order.
+///
+/// [`Map`]: ../struct.Map.html
+/// [`Dense`]: struct.Dense.html
+///
+/// # Examples
+///
+/// ```rust
+/// use fixed_map::{key::Enumerable, Key};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Part {
+///     One,
+///     Two,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Key {
+///     Simple,
+///     Composite(Part),
+///     Option(Option<Part>),
+/// }
+///
+/// assert_eq!(Key::COUNT, 6);
+/// assert_eq!(Key::Composite(Part::Two).ordinal(), 2);
+/// assert_eq!(Key::from_ordinal(3), Some(Key::Option(Some(Part::One))));
+/// assert_eq!(Key::from_ordinal(6), None);
+///
+/// assert_eq!(
+///     Key::all().collect::<Vec<_>>(),
+///     vec![
+///         Key::Simple,
+///         Key::Composite(Part::One),
+///         Key::Composite(Part::Two),
+///         Key::Option(Some(Part::One)),
+///         Key::Option(Some(Part::Two)),
+///         Key::Option(None),
+///     ]
+/// );
+/// ```
+pub trait Enumerable: Copy {
+    /// The number of distinct values of the key.
+    ///
+    /// This has to fit in a `usize`, which limits how large products of keys
+    /// can be. On a 64-bit target `[u8; 7]` has a count of 2<sup>56</sup>,
+    /// while the count of `[u8; 8]` would overflow, so using it fails to
+    /// compile with "Enumerable::COUNT overflows usize".
+    const COUNT: usize;
+
+    /// The ordinal of the key, which is in the range `0..Self::COUNT`.
+    fn ordinal(self) -> usize;
+
+    /// Get the key with the given ordinal, or `None` if it is out of range.
+    fn from_ordinal(ordinal: usize) -> Option<Self>;
+
+    /// An iterator over every value of the key in ordinal order.
+    #[inline]
+    fn all() -> All<Self> {
+        All {
+            front: 0,
+            back: Self::COUNT,
+            key: marker::PhantomData,
+        }
+    }
+}
+
+/// An iterator over every value of an [`Enumerable`] key.
+///
+/// This `struct` is created by the [`all`] method on [`Enumerable`].
+/// See its documentation for more.
+///
+/// [`all`]: trait.Enumerable.html#method.all
+/// [`Enumerable`]: trait.Enumerable.html
+pub struct All<K> {
+    front: usize,
+    back: usize,
+    key: marker::PhantomData<K>,
+}
+
+impl<K> Clone for All<K> {
+    fn clone(&self) -> Self {
+        All {
+            front: self.front,
+            back: self.back,
+            key: marker::PhantomData,
+        }
+    }
+}
+
+impl<K> Iterator for All<K>
+where
+    K: Enumerable,
+{
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let key = K::from_ordinal(self.front);
+        self.front += 1;
+        key
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<K> DoubleEndedIterator for All<K>
+where
+    K: Enumerable,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        K::from_ordinal(self.back)
+    }
+}
+
+impl<K> ExactSizeIterator for All<K> where K: Enumerable {}
+
+impl<K> iter::FusedIterator for All<K> where K: Enumerable {}
+
+impl Enumerable for () {
+    const COUNT: usize = 1;
+
+    #[inline]
+    fn ordinal(self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn from_ordinal(ordinal: usize) -> Option<Self> {
+        match ordinal {
+            0 => Some(()),
+            _ => None,
+        }
+    }
+}
+
+impl Enumerable for bool {
+    const COUNT: usize = 2;
+
+    #[inline]
+    fn ordinal(self) -> usize {
+        match self {
+            true => 0,
+            false => 1,
+        }
+    }
+
+    #[inline]
+    fn from_ordinal(ordinal: usize) -> Option<Self> {
+        match ordinal {
+            0 => Some(true),
+            1 => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl Enumerable for Ordering {
+    const COUNT: usize = 3;
+
+    #[inline]
+    fn ordinal(self) -> usize {
+        match self {
+            Ordering::Less => 0,
+            Ordering::Equal => 1,
+            Ordering::Greater => 2,
+        }
+    }
+
+    #[inline]
+    fn from_ordinal(ordinal: usize) -> Option<Self> {
+        match ordinal {
+            0 => Some(Ordering::Less),
+            1 => Some(Ordering::Equal),
+            2 => Some(Ordering::Greater),
+            _ => None,
+        }
+    }
+}
+
+impl<K> Enumerable for Option<K>
+where
+    K: Enumerable,
+{
+    const COUNT: usize = K::COUNT + 1;
+
+    #[inline]
+    fn ordinal(self) -> usize {
+        match self {
+            Some(key) => key.ordinal(),
+            None => K::COUNT,
+        }
+    }
+
+    #[inline]
+    fn from_ordinal(ordinal: usize) -> Option<Self> {
+        if ordinal < K::COUNT {
+            return K::from_ordinal(ordinal).map(Some);
+        }
+
+        match ordinal - K::COUNT {
+            0 => Some(None),
+            _ => None,
+        }
+    }
+}
+
+impl<A, B> Enumerable for (A, B)
+where
+    A: Enumerable,
+    B: Enumerable,
+{
+    const COUNT: usize = match A::COUNT.checked_mul(B::COUNT) {
+        Some(count) => count,
+        None => panic!("Enumerable::COUNT overflows usize"),
+    };
+
+    #[inline]
+    fn ordinal(self) -> usize {
+        let (a, b) = self;
+        a.ordinal() * B::COUNT + b.ordinal()
+    }
+
+    #[inline]
+    fn from_ordinal(ordinal: usize) -> Option<Self> {
+        if B::COUNT == 0 {
+            return None;
+        }
+
+        let a = A::from_ordinal(ordinal / B::COUNT)?;
+        let b = B::from_ordinal(ordinal % B::COUNT)?;
+        Some((a, b))
+    }
+}
+
+impl<T, E> Enumerable for Result<T, E>
+where
+    T: Enumerable,
+    E: Enumerable,
+{
+    const COUNT: usize = match T::COUNT.checked_add(E::COUNT) {
+        Some(count) => count,
+        None => panic!("Enumerable::COUNT overflows usize"),
+    };
+
+    #[inline]
+    fn ordinal(self) -> usize {
+        match self {
+            Ok(key) => key.ordinal(),
+            Err(key) => T::COUNT + key.ordinal(),
+        }
+    }
+
+    #[inline]
+    fn from_ordinal(ordinal: usize) -> Option<Self> {
+        if ordinal < T::COUNT {
+            return T::from_ordinal(ordinal).map(Ok);
+        }
+
+        E::from_ordinal(ordinal - T::COUNT).map(Err)
+    }
+}
+
+impl Enumerable for u8 {
+    const COUNT: usize = Dense::<u8>::COUNT;
+
+    #[inline]
+    fn ordinal(self) -> usize {
+        Dense(self).ordinal()
+    }
+
+    #[inline]
+    fn from_ordinal(ordinal: usize) -> Option<Self> {
+        Dense::<u8>::from_ordinal(ordinal).map(|Dense(key)| key)
+    }
+}
+
+impl Enumerable for i8 {
+    const COUNT: usize = Dense::<i8>::COUNT;
+
+    #[inline]
+    fn ordinal(self) -> usize {
+        Dense(self).ordinal()
+    }
+
+    #[inline]
+    fn from_ordinal(ordinal: usize) -> Option<Self> {
+        Dense::<i8>::from_ordinal(ordinal).map(|Dense(key)| key)
+    }
 }
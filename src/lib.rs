@@ -9,6 +9,7 @@
 //! For example:
 //!
 //! ```rust
+//! # #[cfg(feature = "alloc")] {
 //! use fixed_map::{Key, Map};
 //!
 //! #[derive(Clone, Copy, Key)]
@@ -42,22 +43,123 @@
 //! assert_eq!(map.get(Key::Number(1)), Some(&4));
 //! assert_eq!(map.get(Key::Number(2)), None);
 //! assert_eq!(map.get(Key::Singleton(())), Some(&5));
+//! # }
 //! ```
 //!
-//! ## Unsafe Use
+//! Enums with only unit variants can opt into storing sets as a bitset with
+//! `#[key(bitset)]`:
 //!
-//! This crate uses unsafe for its iterators.
-//! This is needed because there is no proper way to associate generic lifetimes to associated types.
+//! ```rust
+//! use fixed_map::{Key, Set};
+//!
+//! #[derive(Clone, Copy, Key)]
+//! #[key(bitset)]
+//! enum Dir {
+//!     North,
+//!     East,
+//!     South,
+//!     West,
+//! }
+//!
+//! let mut set = Set::new();
+//! set.insert(Dir::North);
+//! set.insert(Dir::West);
+//!
+//! assert!(set.contains(Dir::North));
+//! assert!(!set.contains(Dir::South));
+//! assert_eq!(std::mem::size_of::<Set<Dir>>(), std::mem::size_of::<u8>());
+//! ```
+//!
+//! Tuples of up to eight keys and arrays of up to sixteen keys are keys as
+//! well. Their values are stored in the storage of each element in turn, so no
+//! hashing is involved unless one of the elements needs it. Note that since
+//! the storage of derived keys is inline, the size of such storage is the
+//! product of the storage of each element:
+//!
+//! ```rust
+//! use fixed_map::{Key, Map};
+//!
+//! #[derive(Clone, Copy, Key)]
+//! enum Side {
+//!     Left,
+//!     Right,
+//! }
+//!
+//! let mut map = Map::new();
+//! map.insert((Side::Left, true), 1);
+//! map.insert((Side::Right, false), 2);
+//!
+//! assert_eq!(map.get((Side::Left, true)), Some(&1));
+//! assert_eq!(map.get((Side::Left, false)), None);
+//! assert_eq!(map.get((Side::Right, false)), Some(&2));
 //!
-//! Instead, we associate the lifetime to the container (`Map` or `Set`) which wraps a set of unsafe derefs over raw //! pointers.
+//! let mut corners = Map::new();
+//! corners.insert([Side::Left, Side::Right], 3);
+//!
+//! assert_eq!(corners.get([Side::Left, Side::Right]), Some(&3));
+//! assert_eq!(corners.get([Side::Right, Side::Left]), None);
+//! ```
+//!
+//! Keys don't have to be `Copy`. Variants can hold owned keys like `String`,
+//! which are stored in a hash map, while the remaining variants keep their
+//! inline storage. Lookups take the key by reference:
+//!
+//! ```rust
+//! # #[cfg(feature = "alloc")] {
+//! use fixed_map::{Key, Map};
+//!
+//! #[derive(Clone, PartialEq, Eq, Key)]
+//! enum Ident {
+//!     SelfValue,
+//!     Custom(String),
+//! }
+//!
+//! let mut map = Map::new();
+//! map.insert(Ident::SelfValue, "self");
+//! map.insert(Ident::Custom(String::from("foo")), "foo");
+//!
+//! let foo = Ident::Custom(String::from("foo"));
+//! assert_eq!(map.get(&Ident::SelfValue), Some(&"self"));
+//! assert_eq!(map.get(&foo), Some(&"foo"));
+//! assert_eq!(map.remove(&foo), Some("foo"));
+//! assert_eq!(map.get(&foo), None);
+//! # }
+//! ```
+//!
+//! ## Features
+//!
+//! - `std` (default) - Implements `std::error::Error` for the errors of this
+//!   crate. Enables `alloc`.
+//! - `alloc` - Keys whose storage is allocated, which are the keys stored in a
+//!   hash map like `u32`, `char`, `&'static str` and `String`, and the keys
+//!   stored densely like `Dense<u16>`. Without it the crate only depends on
+//!   `core`, and keys such as derived enums, `bool`, `u8`, `Option` and
+//!   tuples of them are stored inline. Variants of derived enums can still
+//!   hold hashed keys like `u32` by storing them in a fixed number of inline
+//!   slots with `#[key(inline = N)]`.
+//! - `serde` - Implements `Serialize` and `Deserialize` for `Map` (as a map)
+//!   and `Set` (as a sequence) when the key and value implement them.
 
+#![no_std]
 #![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
 
 pub mod key;
 pub mod map;
+pub mod option_bucket;
+#[cfg(feature = "serde")]
+mod serde;
 pub mod set;
 pub mod storage;
+pub mod total_map;
 
 pub use self::map::Map;
 pub use self::set::Set;
+pub use self::total_map::TotalMap;
 pub use fixed_map_derive::Key;
@@ -1,14 +1,21 @@
 //! Contains the fixed `Map` implementation.
-use std::marker;
-
 use crate::{key::Key, storage::Storage};
-use std::ops::Index;
+use core::borrow::Borrow;
+use core::iter;
+use core::marker;
+use core::mem;
+use core::ops::Index;
+
+mod entry;
+
+pub use self::entry::{Entry, OccupiedEntry, VacantEntry};
 
 /// A fixed map with a predetermined size.
 ///
 /// # Examples
 ///
 /// ```rust
+/// # #[cfg(feature = "alloc")] {
 /// use fixed_map::{Key, Map};
 ///
 /// #[derive(Clone, Copy, Key)]
@@ -52,6 +59,7 @@ use std::ops::Index;
 /// assert_eq!(map.get(Key::Option(Some(Part::Two))), None);
 /// assert_eq!(map.get(Key::Boolean(true)), Some(&8));
 /// assert_eq!(map.get(Key::Boolean(false)), None);
+/// # }
 /// ```
 ///
 /// Storing references:
@@ -253,7 +261,6 @@ where
     pub fn iter<'a>(&'a self) -> Iter<'a, K, V> {
         Iter {
             iter: self.storage.iter(),
-            marker: marker::PhantomData,
         }
     }
 
@@ -287,12 +294,72 @@ where
     pub fn iter_mut<'a>(&'a mut self) -> IterMut<'a, K, V> {
         IterMut {
             iter: self.storage.iter_mut(),
-            marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Creates a consuming iterator visiting all the keys in arbitrary order.
+    /// The map cannot be used after calling this.
+    /// The iterator element type is `K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::One, 1);
+    /// map.insert(Key::Two, 2);
+    ///
+    /// assert_eq!(map.into_keys().collect::<Vec<_>>(), vec![Key::One, Key::Two]);
+    /// ```
+    #[inline]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys {
+            inner: self.into_iter(),
+        }
+    }
+
+    /// Creates a consuming iterator visiting all the values in arbitrary order.
+    /// The map cannot be used after calling this.
+    /// The iterator element type is `V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::One, String::from("a"));
+    /// map.insert(Key::Two, String::from("b"));
+    ///
+    /// assert_eq!(map.into_values().collect::<Vec<_>>(), vec![String::from("a"), String::from("b")]);
+    /// ```
+    #[inline]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues {
+            inner: self.into_iter(),
         }
     }
 
     /// Returns a reference to the value corresponding to the key.
     ///
+    /// The key can be given either by value or by reference, so that keys
+    /// which aren't `Copy` can be looked up without giving them away.
+    ///
     /// # Examples
     ///
     /// ```
@@ -307,11 +374,15 @@ where
     /// let mut map = Map::new();
     /// map.insert(Key::One, "a");
     /// assert_eq!(map.get(Key::One), Some(&"a"));
+    /// assert_eq!(map.get(&Key::One), Some(&"a"));
     /// assert_eq!(map.get(Key::Two), None);
     /// ```
     #[inline]
-    pub fn get(&self, key: K) -> Option<&V> {
-        self.storage.get(key)
+    pub fn get<Q>(&self, key: Q) -> Option<&V>
+    where
+        Q: Borrow<K>,
+    {
+        self.storage.get(key.borrow())
     }
 
     /// Returns a mutable reference to the value corresponding to the key.
@@ -335,8 +406,11 @@ where
     /// assert_eq!(map.get(Key::One), Some(&"b"));
     /// ```
     #[inline]
-    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
-        self.storage.get_mut(key)
+    pub fn get_mut<Q>(&mut self, key: Q) -> Option<&mut V>
+    where
+        Q: Borrow<K>,
+    {
+        self.storage.get_mut(key.borrow())
     }
 
     /// Inserts a key-value pair into the map.
@@ -346,6 +420,13 @@ where
     /// If the map did have this key present, the value is updated, and the old
     /// value is returned.
     ///
+    /// # Panics
+    ///
+    /// Panics if the storage of the key has a fixed capacity which is already
+    /// used up. See [`try_insert`] for a fallible alternative.
+    ///
+    /// [`try_insert`]: struct.Map.html#method.try_insert
+    ///
     /// # Examples
     ///
     /// ```
@@ -370,6 +451,46 @@ where
         self.storage.insert(key, value)
     }
 
+    /// Inserts a key-value pair into the map, unless the storage of the key is
+    /// full.
+    ///
+    /// This is [`insert`] for keys stored with a fixed capacity, like variants
+    /// marked with `#[key(inline = N)]`, where [`insert`] panics when there is
+    /// no room for a new key. Instead, the key and value are handed back in a
+    /// [`CapacityError`]. Replacing the value of a key which is already
+    /// present always succeeds.
+    ///
+    /// [`insert`]: struct.Map.html#method.insert
+    /// [`CapacityError`]: struct.CapacityError.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     #[key(inline = 2)]
+    ///     Number(u32),
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.try_insert(Key::Number(1), "a"), Ok(None));
+    /// assert_eq!(map.try_insert(Key::Number(2), "b"), Ok(None));
+    /// assert_eq!(map.try_insert(Key::Number(1), "c"), Ok(Some("a")));
+    ///
+    /// let error = map.try_insert(Key::Number(3), "d").unwrap_err();
+    /// assert_eq!(error.into_inner(), (Key::Number(3), "d"));
+    ///
+    /// assert_eq!(map.try_insert(Key::First, "e"), Ok(None));
+    /// assert_eq!(map.len(), 3);
+    /// ```
+    #[inline]
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<K, V>> {
+        self.storage.try_insert(key, value)
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
@@ -390,8 +511,74 @@ where
     /// assert_eq!(map.remove(Key::One), None);
     /// ```
     #[inline]
-    pub fn remove(&mut self, key: K) -> Option<V> {
-        self.storage.remove(key)
+    pub fn remove<Q>(&mut self, key: Q) -> Option<V>
+    where
+        Q: Borrow<K>,
+    {
+        self.storage.remove(key.borrow())
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place
+    /// manipulation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     Even,
+    ///     Odd,
+    /// }
+    ///
+    /// let mut map: Map<Key, u32> = Map::new();
+    ///
+    /// for n in [3, 45, 3, 23, 2, 10, 59, 11, 51, 70] {
+    ///     map
+    ///         .entry(if n % 2 == 0 { Key::Even } else { Key::Odd })
+    ///         .and_modify(|x| *x += 1)
+    ///         .or_insert(1);
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::Even), Some(&3));
+    /// assert_eq!(map.get(Key::Odd), Some(&7));
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Part {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     Simple,
+    ///     Composite(Part),
+    ///     Number(u32),
+    /// }
+    ///
+    /// let mut map: Map<Key, Vec<u32>> = Map::new();
+    ///
+    /// map.entry(Key::Composite(Part::One)).or_default().push(1);
+    /// map.entry(Key::Number(4)).or_default().push(2);
+    /// map.entry(Key::Composite(Part::One)).or_default().push(3);
+    ///
+    /// assert_eq!(map.get(Key::Composite(Part::One)), Some(&vec![1, 3]));
+    /// assert_eq!(map.get(Key::Composite(Part::Two)), None);
+    /// assert_eq!(map.get(Key::Number(4)), Some(&vec![2]));
+    /// assert_eq!(map.get(Key::Simple), None);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K::Storage, K, V> {
+        self.storage.entry(key)
     }
 
     /// Clears the map, removing all key-value pairs. Keeps the allocated memory
@@ -418,6 +605,101 @@ where
         self.storage.clear()
     }
 
+    /// Clears the map, returning all key-value pairs as an iterator.
+    ///
+    /// The map is left empty once this method returns, even if the
+    /// returned iterator is not consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::One, "a");
+    /// map.insert(Key::Two, "b");
+    ///
+    /// assert_eq!(map.drain().collect::<Vec<_>>(), vec![(Key::One, "a"), (Key::Two, "b")]);
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        Drain {
+            inner: mem::take(&mut self.storage).into_iter(),
+            marker: marker::PhantomData,
+        }
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all pairs `(k, v)` such that `f(k, &mut v)`
+    /// returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, 1);
+    /// map.insert(Key::Second, 2);
+    /// map.insert(Key::Third, 3);
+    ///
+    /// map.retain(|k, v| {
+    ///     *v *= 10;
+    ///     k != Key::Second
+    /// });
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(Key::First, &10), (Key::Third, &30)]);
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     Simple,
+    ///     Number(u32),
+    ///     Boolean(bool),
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::Simple, 1);
+    /// map.insert(Key::Number(2), 2);
+    /// map.insert(Key::Number(3), 3);
+    /// map.insert(Key::Boolean(true), 4);
+    ///
+    /// map.retain(|_, v| *v % 2 == 0);
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get(Key::Number(2)), Some(&2));
+    /// assert_eq!(map.get(Key::Boolean(true)), Some(&4));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        self.storage.retain(f);
+    }
+
     /// Returns true if the map contains no elements.
     ///
     /// # Examples
@@ -436,8 +718,9 @@ where
     /// map.insert(Key::One, "a");
     /// assert!(!map.is_empty());
     /// ```
+    #[inline]
     pub fn is_empty(&self) -> bool {
-        self.iter().next().is_none()
+        self.storage.is_empty()
     }
 
     /// Returns the number of elements in the map.
@@ -458,8 +741,9 @@ where
     /// map.insert(Key::One, "a");
     /// assert_eq!(map.len(), 1);
     /// ```
+    #[inline]
     pub fn len(&self) -> usize {
-        self.iter().count()
+        self.storage.len()
     }
 }
 
@@ -484,12 +768,12 @@ where
     }
 }
 
-impl<K, V> std::fmt::Debug for Map<K, V>
+impl<K, V> core::fmt::Debug for Map<K, V>
 where
-    K: Key<K, V> + std::fmt::Debug,
-    V: std::fmt::Debug,
+    K: Key<K, V> + core::fmt::Debug,
+    V: core::fmt::Debug,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         let mut debug_map = f.debug_map();
         for (k, v) in self.iter() {
             debug_map.entry(&k, v);
@@ -548,6 +832,255 @@ where
     }
 }
 
+impl<K, V> iter::FromIterator<(K, V)> for Map<K, V>
+where
+    K: Key<K, V>,
+{
+    /// Creates a `Map` from an iterator of key-value pairs.
+    ///
+    /// If a key is yielded more than once, the last value wins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a key has a fixed capacity which is already
+    /// used up. See [`Map::try_insert`] for a fallible alternative.
+    ///
+    /// [`Map::try_insert`]: struct.Map.html#method.try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let map: Map<_, _> = vec![(Key::One, 1), (Key::Three, 3)].into_iter().collect();
+    ///
+    /// assert_eq!(map.get(Key::One), Some(&1));
+    /// assert_eq!(map.get(Key::Two), None);
+    /// assert_eq!(map.get(Key::Three), Some(&3));
+    /// ```
+    #[inline]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = Map::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for Map<K, V>
+where
+    K: Key<K, V>,
+{
+    /// Inserts all key-value pairs from the iterator into the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a key has a fixed capacity which is already
+    /// used up. See [`Map::try_insert`] for a fallible alternative.
+    ///
+    /// [`Map::try_insert`]: struct.Map.html#method.try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::One, 1);
+    /// map.extend(vec![(Key::One, 10), (Key::Two, 20)]);
+    ///
+    /// assert_eq!(map.get(Key::One), Some(&10));
+    /// assert_eq!(map.get(Key::Two), Some(&20));
+    /// ```
+    #[inline]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        for (k, v) in iter {
+            self.storage.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K, V> Extend<(&'a K, &'a V)> for Map<K, V>
+where
+    K: Key<K, V> + Copy,
+    V: Copy,
+{
+    /// Inserts all key-value pairs from the iterator into the map, copying
+    /// them out of the references.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a key has a fixed capacity which is already
+    /// used up. See [`Map::try_insert`] for a fallible alternative.
+    ///
+    /// [`Map::try_insert`]: struct.Map.html#method.try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let pairs = [(Key::One, 1), (Key::Two, 2)];
+    ///
+    /// let mut map = Map::new();
+    /// map.extend(pairs.iter().map(|(k, v)| (k, v)));
+    ///
+    /// assert_eq!(map.get(Key::One), Some(&1));
+    /// assert_eq!(map.get(Key::Two), Some(&2));
+    /// ```
+    #[inline]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (&'a K, &'a V)>,
+    {
+        for (k, v) in iter {
+            self.storage.insert(*k, *v);
+        }
+    }
+}
+
+impl<K, V> IntoIterator for Map<K, V>
+where
+    K: Key<K, V>,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    /// Creates a consuming iterator visiting all the key-value pairs in
+    /// arbitrary order. The map cannot be used after calling this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::One, String::from("a"));
+    /// map.insert(Key::Two, String::from("b"));
+    ///
+    /// let vec: Vec<(Key, String)> = map.into_iter().collect();
+    /// assert_eq!(vec, vec![(Key::One, String::from("a")), (Key::Two, String::from("b"))]);
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.storage.into_iter(),
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a> IntoIterator for &'a Map<K, V>
+where
+    K: Key<K, V>,
+{
+    type Item = (K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: 'a, V: 'a> IntoIterator for &'a mut Map<K, V>
+where
+    K: Key<K, V>,
+{
+    type Item = (K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// The error returned by [`Map::try_insert`] when the storage of the key has
+/// no room left for it. It holds the key and value which couldn't be inserted.
+///
+/// [`Map::try_insert`]: struct.Map.html#method.try_insert
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K, V> CapacityError<K, V> {
+    /// Construct an error for the key and value which couldn't be inserted.
+    ///
+    /// This is used by storage implementations, including the ones generated
+    /// by the `Key` derive.
+    #[inline]
+    pub fn new(key: K, value: V) -> Self {
+        CapacityError { key, value }
+    }
+
+    /// The key which couldn't be inserted.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// The value which couldn't be inserted.
+    #[inline]
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Get back the key and value which couldn't be inserted.
+    #[inline]
+    pub fn into_inner(self) -> (K, V) {
+        (self.key, self.value)
+    }
+}
+
+impl<K, V> core::fmt::Display for CapacityError<K, V>
+where
+    K: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "no capacity left for key {:?}", self.key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V> std::error::Error for CapacityError<K, V>
+where
+    K: core::fmt::Debug,
+    V: core::fmt::Debug,
+{
+}
+
 /// An iterator over the entries of a `Map`.
 ///
 /// This `struct` is created by the [`iter`] method on [`Map`]. See its
@@ -555,22 +1088,20 @@ where
 ///
 /// [`iter`]: struct.Map.html#method.iter
 /// [`Map`]: struct.Map.html
-pub struct Iter<'a, K, V: 'a>
+pub struct Iter<'a, K: 'a, V: 'a>
 where
     K: Key<K, V>,
 {
-    iter: <K::Storage as Storage<K, V>>::Iter,
-    marker: marker::PhantomData<&'a ()>,
+    iter: <K::Storage as Storage<K, V>>::Iter<'a>,
 }
 
-impl<'a, K, V: 'a> Clone for Iter<'a, K, V>
+impl<'a, K: 'a, V: 'a> Clone for Iter<'a, K, V>
 where
     K: Key<K, V>,
 {
     fn clone(&self) -> Iter<'a, K, V> {
         Iter {
             iter: self.iter.clone(),
-            marker: marker::PhantomData,
         }
     }
 }
@@ -582,7 +1113,7 @@ where
     type Item = (K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|(k, v)| (k, unsafe { &*v }))
+        self.iter.next()
     }
 }
 
@@ -593,22 +1124,21 @@ where
 ///
 /// [`iter_mut`]: struct.Map.html#method.iter_mut
 /// [`Map`]: struct.Map.html
-pub struct IterMut<'a, K, V: 'a>
+pub struct IterMut<'a, K: 'a, V: 'a>
 where
     K: Key<K, V>,
 {
-    iter: <K::Storage as Storage<K, V>>::IterMut,
-    marker: marker::PhantomData<&'a ()>,
+    iter: <K::Storage as Storage<K, V>>::IterMut<'a>,
 }
 
-impl<'a, K, V: 'a> Iterator for IterMut<'a, K, V>
+impl<'a, K: 'a, V: 'a> Iterator for IterMut<'a, K, V>
 where
     K: Key<K, V>,
 {
     type Item = (K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|(k, v)| (k, unsafe { &mut *v }))
+        self.iter.next()
     }
 }
 
@@ -620,7 +1150,7 @@ where
 /// [`keys`]: struct.Map.html#method.keys
 /// [`Map`]: struct.Map.html
 #[derive(Clone)]
-pub struct Keys<'a, K, V: 'a>
+pub struct Keys<'a, K: 'a, V: 'a>
 where
     K: Key<K, V>,
 {
@@ -646,7 +1176,7 @@ where
 /// [`values`]: struct.Map.html#method.values
 /// [`Map`]: struct.Map.html
 #[derive(Clone)]
-pub struct Values<'a, K, V: 'a>
+pub struct Values<'a, K: 'a, V: 'a>
 where
     K: Key<K, V>,
 {
@@ -671,7 +1201,7 @@ where
 ///
 /// [`values_mut`]: struct.Map.html#method.values_mut
 /// [`Map`]: struct.Map.html
-pub struct ValuesMut<'a, K, V: 'a>
+pub struct ValuesMut<'a, K: 'a, V: 'a>
 where
     K: Key<K, V>,
 {
@@ -688,3 +1218,104 @@ where
         self.inner.next().map(|(_, v)| v)
     }
 }
+
+/// An owning iterator over the entries of a `Map`.
+///
+/// This `struct` is created by the [`into_iter`] method on [`Map`]
+/// (provided by the `IntoIterator` trait). See its documentation for more.
+///
+/// [`into_iter`]: struct.Map.html#method.into_iter
+/// [`Map`]: struct.Map.html
+pub struct IntoIter<K, V>
+where
+    K: Key<K, V>,
+{
+    inner: <K::Storage as Storage<K, V>>::IntoIter,
+}
+
+impl<K, V> Iterator for IntoIter<K, V>
+where
+    K: Key<K, V>,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// An owning iterator over the keys of a `Map`.
+///
+/// This `struct` is created by the [`into_keys`] method on [`Map`].
+/// See its documentation for more.
+///
+/// [`into_keys`]: struct.Map.html#method.into_keys
+/// [`Map`]: struct.Map.html
+pub struct IntoKeys<K, V>
+where
+    K: Key<K, V>,
+{
+    inner: IntoIter<K, V>,
+}
+
+impl<K, V> Iterator for IntoKeys<K, V>
+where
+    K: Key<K, V>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+}
+
+/// An owning iterator over the values of a `Map`.
+///
+/// This `struct` is created by the [`into_values`] method on [`Map`].
+/// See its documentation for more.
+///
+/// [`into_values`]: struct.Map.html#method.into_values
+/// [`Map`]: struct.Map.html
+pub struct IntoValues<K, V>
+where
+    K: Key<K, V>,
+{
+    inner: IntoIter<K, V>,
+}
+
+impl<K, V> Iterator for IntoValues<K, V>
+where
+    K: Key<K, V>,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, v)| v)
+    }
+}
+
+/// A draining iterator over the entries of a `Map`.
+///
+/// This `struct` is created by the [`drain`] method on [`Map`].
+/// See its documentation for more.
+///
+/// [`drain`]: struct.Map.html#method.drain
+/// [`Map`]: struct.Map.html
+pub struct Drain<'a, K, V>
+where
+    K: Key<K, V>,
+{
+    inner: <K::Storage as Storage<K, V>>::IntoIter,
+    marker: marker::PhantomData<&'a mut ()>,
+}
+
+impl<'a, K, V> Iterator for Drain<'a, K, V>
+where
+    K: Key<K, V>,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
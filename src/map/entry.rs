@@ -0,0 +1,584 @@
+//! Contains the entry API for `Map`.
+
+use crate::map::CapacityError;
+use crate::storage::Storage;
+
+/// A view into an occupied entry in a `Map`.
+/// It is part of the [`Entry`] enum.
+///
+/// [`Entry`]: enum.Entry.html
+pub trait OccupiedEntry<'a, K, V> {
+    /// Gets a copy of the key in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First, 0);
+    ///
+    /// if let Entry::Occupied(occupied) = map.entry(Key::First) {
+    ///     assert_eq!(occupied.key(), Key::First);
+    /// }
+    /// ```
+    fn key(&self) -> K;
+
+    /// Gets a reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First, 12);
+    ///
+    /// if let Entry::Occupied(occupied) = map.entry(Key::First) {
+    ///     assert_eq!(occupied.get(), &12);
+    /// }
+    /// ```
+    fn get(&self) -> &V;
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// If you need a reference to the `OccupiedEntry` which may outlive the
+    /// destruction of the `Entry` value, see [`into_mut`].
+    ///
+    /// [`into_mut`]: trait.OccupiedEntry.html#tymethod.into_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First, 12);
+    ///
+    /// if let Entry::Occupied(mut occupied) = map.entry(Key::First) {
+    ///     *occupied.get_mut() *= 2;
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::First), Some(&24));
+    /// ```
+    fn get_mut(&mut self) -> &mut V;
+
+    /// Converts the `OccupiedEntry` into a mutable reference to the value in
+    /// the entry with a lifetime bound to the map itself.
+    ///
+    /// If you need multiple references to the `OccupiedEntry`, see
+    /// [`get_mut`].
+    ///
+    /// [`get_mut`]: trait.OccupiedEntry.html#tymethod.get_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First, 12);
+    ///
+    /// if let Entry::Occupied(occupied) = map.entry(Key::First) {
+    ///     *occupied.into_mut() += 10;
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::First), Some(&22));
+    /// ```
+    fn into_mut(self) -> &'a mut V;
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First, 12);
+    ///
+    /// if let Entry::Occupied(mut occupied) = map.entry(Key::First) {
+    ///     assert_eq!(occupied.insert(10), 12);
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::First), Some(&10));
+    /// ```
+    fn insert(&mut self, value: V) -> V;
+
+    /// Takes the value out of the entry, and returns it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First, 12);
+    ///
+    /// if let Entry::Occupied(occupied) = map.entry(Key::First) {
+    ///     assert_eq!(occupied.remove(), 12);
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::First), None);
+    /// ```
+    fn remove(self) -> V;
+
+    /// Takes the key and the value out of the entry, and returns them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First, 12);
+    ///
+    /// if let Entry::Occupied(occupied) = map.entry(Key::First) {
+    ///     assert_eq!(occupied.remove_entry(), (Key::First, 12));
+    /// }
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    fn remove_entry(self) -> (K, V)
+    where
+        Self: Sized,
+    {
+        let key = self.key();
+        (key, self.remove())
+    }
+}
+
+/// A view into a vacant entry in a `Map`.
+/// It is part of the [`Entry`] enum.
+///
+/// [`Entry`]: enum.Entry.html
+pub trait VacantEntry<'a, K, V> {
+    /// Gets a copy of the key that would be used when inserting a value
+    /// through the `VacantEntry`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, VacantEntry};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// if let Entry::Vacant(vacant) = map.entry(Key::First) {
+    ///     assert_eq!(vacant.key(), Key::First);
+    /// }
+    /// ```
+    fn key(&self) -> K;
+
+    /// Sets the value of the entry with the `VacantEntry`'s key,
+    /// and returns a mutable reference to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of the key has a fixed capacity which is already
+    /// used up. See [`try_insert`] for a fallible alternative.
+    ///
+    /// [`try_insert`]: trait.VacantEntry.html#method.try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, VacantEntry};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// if let Entry::Vacant(vacant) = map.entry(Key::First) {
+    ///     assert_eq!(*vacant.insert(37), 37);
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::First), Some(&37));
+    /// ```
+    fn insert(self, value: V) -> &'a mut V;
+
+    /// Tries to set the value of the entry with the `VacantEntry`'s key, and
+    /// returns a mutable reference to it.
+    ///
+    /// If the storage of the key has a fixed capacity which is already used
+    /// up, the key and the value are handed back in a [`CapacityError`].
+    ///
+    /// [`CapacityError`]: struct.CapacityError.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, VacantEntry};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     #[key(inline = 1)]
+    ///     Number(u32),
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// if let Entry::Vacant(vacant) = map.entry(Key::Number(1)) {
+    ///     assert_eq!(vacant.try_insert(37).map(|value| *value), Ok(37));
+    /// }
+    ///
+    /// if let Entry::Vacant(vacant) = map.entry(Key::Number(2)) {
+    ///     let error = vacant.try_insert(42).unwrap_err();
+    ///     assert_eq!(error.into_inner(), (Key::Number(2), 42));
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::Number(1)), Some(&37));
+    /// assert_eq!(map.get(Key::Number(2)), None);
+    /// ```
+    #[inline]
+    fn try_insert(self, value: V) -> Result<&'a mut V, CapacityError<K, V>>
+    where
+        Self: Sized,
+    {
+        Ok(self.insert(value))
+    }
+}
+
+/// A view into a single entry in a map, which may either be vacant or occupied.
+///
+/// This `enum` is constructed from the [`entry`] method on [`Map`].
+///
+/// [`entry`]: struct.Map.html#method.entry
+/// [`Map`]: struct.Map.html
+pub enum Entry<'a, S: 'a, K, V>
+where
+    S: Storage<K, V>,
+{
+    /// An occupied entry.
+    Occupied(S::Occupied<'a>),
+    /// A vacant entry.
+    Vacant(S::Vacant<'a>),
+}
+
+impl<'a, S: 'a, K, V> Entry<'a, S, K, V>
+where
+    S: Storage<K, V>,
+{
+    /// Ensures a value is in the entry by inserting the default if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry is vacant and the storage of its key has a fixed
+    /// capacity which is already used up. See [`or_try_insert`] for a
+    /// fallible alternative.
+    ///
+    /// [`or_try_insert`]: enum.Entry.html#method.or_try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// map.entry(Key::First).or_insert(3);
+    /// assert_eq!(map.get(Key::First), Some(&3));
+    ///
+    /// *map.entry(Key::First).or_insert(10) *= 2;
+    /// assert_eq!(map.get(Key::First), Some(&6));
+    /// ```
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Tries to ensure a value is in the entry by inserting the default if
+    /// empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// If the entry is vacant and the storage of its key has a fixed capacity
+    /// which is already used up, the key and the default are handed back in
+    /// a [`CapacityError`].
+    ///
+    /// [`CapacityError`]: struct.CapacityError.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     #[key(inline = 1)]
+    ///     Number(u32),
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// *map.entry(Key::Number(1)).or_try_insert(3).unwrap() *= 2;
+    /// assert_eq!(map.entry(Key::Number(1)).or_try_insert(10).map(|v| *v), Ok(6));
+    ///
+    /// let error = map.entry(Key::Number(2)).or_try_insert(4).unwrap_err();
+    /// assert_eq!(error.into_inner(), (Key::Number(2), 4));
+    /// ```
+    #[inline]
+    pub fn or_try_insert(self, default: V) -> Result<&'a mut V, CapacityError<K, V>> {
+        match self {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => entry.try_insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns a mutable reference to the value in the
+    /// entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry is vacant and the storage of its key has a fixed
+    /// capacity which is already used up. See [`or_try_insert`] for a
+    /// fallible alternative.
+    ///
+    /// [`or_try_insert`]: enum.Entry.html#method.or_try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, String> = Map::new();
+    /// let s = "hoho".to_string();
+    ///
+    /// map.entry(Key::First).or_insert_with(|| s);
+    /// assert_eq!(map.get(Key::First), Some(&"hoho".to_string()));
+    /// ```
+    #[inline]
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of
+    /// the default function. This method allows for generating key-derived
+    /// values for insertion by providing the default function a copy of the
+    /// key that was passed to the `.entry(key)` method call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry is vacant and the storage of its key has a fixed
+    /// capacity which is already used up. See [`or_try_insert`] for a
+    /// fallible alternative.
+    ///
+    /// [`or_try_insert`]: enum.Entry.html#method.or_try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, String> = Map::new();
+    ///
+    /// map.entry(Key::First).or_insert_with_key(|k| format!("{:?} = {}", k, 1));
+    /// assert_eq!(map.get(Key::First), Some(&"First = 1".to_string()));
+    /// ```
+    #[inline]
+    pub fn or_insert_with_key<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce(K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Returns a copy of this entry's key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// assert_eq!(map.entry(Key::First).key(), Key::First);
+    /// ```
+    #[inline]
+    pub fn key(&self) -> K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// map.entry(Key::First)
+    ///    .and_modify(|e| { *e += 1 })
+    ///    .or_insert(42);
+    /// assert_eq!(map.get(Key::First), Some(&42));
+    ///
+    /// map.entry(Key::First)
+    ///    .and_modify(|e| { *e += 1 })
+    ///    .or_insert(42);
+    /// assert_eq!(map.get(Key::First), Some(&43));
+    /// ```
+    #[inline]
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default value if
+    /// empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry is vacant and the storage of its key has a fixed
+    /// capacity which is already used up. See [`or_try_insert`] for a
+    /// fallible alternative.
+    ///
+    /// [`or_try_insert`]: enum.Entry.html#method.or_try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// map.entry(Key::First).or_default();
+    /// assert_eq!(map.get(Key::First), Some(&0));
+    /// ```
+    #[inline]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(V::default()),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Helpers to build entries over a slot of type `Option<T>`.
+//!
+//! These are used by the storage implementations in this crate and by the
+//! code generated by the `Key` derive to implement the entry API.
+
+/// A slot of type `Option<T>` which is known to be either occupied or vacant.
+pub enum OptionBucket<'a, T> {
+    /// The slot is occupied.
+    Some(SomeBucket<'a, T>),
+    /// The slot is vacant.
+    None(NoneBucket<'a, T>),
+}
+
+impl<'a, T> OptionBucket<'a, T> {
+    /// Inspect the given slot and construct the matching bucket for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::option_bucket::OptionBucket;
+    ///
+    /// let mut slot = Some(1);
+    ///
+    /// match OptionBucket::new(&mut slot) {
+    ///     OptionBucket::Some(mut some) => *some.get_mut() += 1,
+    ///     OptionBucket::None(_) => unreachable!(),
+    /// }
+    ///
+    /// assert_eq!(slot, Some(2));
+    /// ```
+    #[inline]
+    pub fn new(outer: &'a mut Option<T>) -> Self {
+        if outer.is_some() {
+            OptionBucket::Some(SomeBucket { outer })
+        } else {
+            OptionBucket::None(NoneBucket { outer })
+        }
+    }
+}
+
+/// A slot of type `Option<T>` which is known to be occupied.
+pub struct SomeBucket<'a, T> {
+    outer: &'a mut Option<T>,
+}
+
+impl<'a, T> SomeBucket<'a, T> {
+    /// Get a reference to the value in the slot.
+    #[inline]
+    pub fn get(&self) -> &T {
+        match &*self.outer {
+            Some(value) => value,
+            None => unreachable!("bucket is occupied"),
+        }
+    }
+
+    /// Get a mutable reference to the value in the slot.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        match self.outer {
+            Some(value) => value,
+            None => unreachable!("bucket is occupied"),
+        }
+    }
+
+    /// Convert the bucket into a mutable reference to the value in the slot,
+    /// bound by the lifetime of the slot.
+    #[inline]
+    pub fn into_mut(self) -> &'a mut T {
+        match self.outer {
+            Some(value) => value,
+            None => unreachable!("bucket is occupied"),
+        }
+    }
+
+    /// Replace the value in the slot, returning the old value.
+    #[inline]
+    pub fn replace(&mut self, value: T) -> T {
+        match self.outer.replace(value) {
+            Some(value) => value,
+            None => unreachable!("bucket is occupied"),
+        }
+    }
+
+    /// Take the value out of the slot, leaving it vacant.
+    #[inline]
+    pub fn take(self) -> T {
+        match self.outer.take() {
+            Some(value) => value,
+            None => unreachable!("bucket is occupied"),
+        }
+    }
+}
+
+/// A slot of type `Option<T>` which is known to be vacant.
+pub struct NoneBucket<'a, T> {
+    outer: &'a mut Option<T>,
+}
+
+impl<'a, T> NoneBucket<'a, T> {
+    /// Insert a value into the slot, returning a mutable reference to it.
+    #[inline]
+    pub fn insert(self, value: T) -> &'a mut T {
+        self.outer.insert(value)
+    }
+}
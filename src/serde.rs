@@ -0,0 +1,135 @@
+//! `Serialize` and `Deserialize` implementations for `Map` and `Set`.
+//!
+//! A `Map` is serialized as a map and a `Set` as a sequence, so they are
+//! compatible with the serialized forms of `HashMap` and `HashSet`.
+
+use crate::{key::Key, map::Map, set::Set};
+use ::serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use core::fmt;
+use core::marker;
+
+impl<K, V> Serialize for Map<K, V>
+where
+    K: Key<K, V> + Serialize,
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+
+        for (k, v) in self.iter() {
+            map.serialize_entry(&k, v)?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for Map<K, V>
+where
+    K: Key<K, V> + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MapVisitor {
+            marker: marker::PhantomData,
+        })
+    }
+}
+
+struct MapVisitor<K, V> {
+    marker: marker::PhantomData<(K, V)>,
+}
+
+impl<'de, K, V> Visitor<'de> for MapVisitor<K, V>
+where
+    K: Key<K, V> + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = Map<K, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut map = Map::new();
+
+        while let Some((k, v)) = access.next_entry()? {
+            map.try_insert(k, v)
+                .map_err(|_| de::Error::custom("no capacity left for key"))?;
+        }
+
+        Ok(map)
+    }
+}
+
+impl<K> Serialize for Set<K>
+where
+    K: Key<K, ()> + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+
+        for k in self.iter() {
+            seq.serialize_element(&k)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl<'de, K> Deserialize<'de> for Set<K>
+where
+    K: Key<K, ()> + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SetVisitor {
+            marker: marker::PhantomData,
+        })
+    }
+}
+
+struct SetVisitor<K> {
+    marker: marker::PhantomData<K>,
+}
+
+impl<'de, K> Visitor<'de> for SetVisitor<K>
+where
+    K: Key<K, ()> + Deserialize<'de>,
+{
+    type Value = Set<K>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut set = Set::new();
+
+        while let Some(k) = access.next_element()? {
+            set.try_insert(k)
+                .map_err(|_| de::Error::custom("no capacity left for key"))?;
+        }
+
+        Ok(set)
+    }
+}
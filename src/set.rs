@@ -1,11 +1,22 @@
 //! Contains the fixed `Set` implementation.
-use crate::{key::Key, storage::Storage};
+use crate::{key::Key, map::CapacityError, storage::SetStorage};
+use core::borrow::Borrow;
+use core::iter;
+use core::marker;
+use core::mem;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
 
 /// A fixed set implemented as a `Map` where the value is `()`.
 ///
+/// Keys which provide a more compact [`SetStorage`] use that instead, like
+/// enums deriving `Key` with `#[key(bitset)]`.
+///
+/// [`SetStorage`]: storage/trait.SetStorage.html
+///
 /// # Examples
 ///
 /// ```rust
+/// # #[cfg(feature = "alloc")] {
 /// use fixed_map::{Key, Set};
 ///
 /// #[derive(Clone, Copy, Key)]
@@ -49,12 +60,13 @@ use crate::{key::Key, storage::Storage};
 /// assert!(!set.contains(Key::Option(Some(Part::Two))));
 /// assert!(set.contains(Key::Boolean(true)));
 /// assert!(!set.contains(Key::Boolean(false)));
+/// # }
 /// ```
 pub struct Set<K>
 where
     K: Key<K, ()>,
 {
-    storage: K::Storage,
+    storage: K::SetStorage,
 }
 
 /// A map implementation that uses fixed storage.
@@ -122,20 +134,13 @@ where
     #[inline]
     pub fn new() -> Set<K> {
         Set {
-            storage: K::Storage::default(),
+            storage: K::SetStorage::default(),
         }
     }
 
     /// An iterator visiting all values in arbitrary order.
     /// The iterator element type is `K`.
     ///
-    /// Because of limitations in how Rust can express lifetimes through traits, this method will
-    /// first pre-allocate a vector to store all references.
-    ///
-    /// For a zero-cost version of this function, see [`Set::iter_fn`].
-    ///
-    /// [`Set::iter_fn`]: struct.Set.html#method.iter_fn
-    ///
     /// # Examples
     ///
     /// ```
@@ -154,7 +159,7 @@ where
     ///
     /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![Key::One, Key::Two]);
     /// ```
-    pub fn iter(&self) -> Iter<K> {
+    pub fn iter(&self) -> Iter<'_, K> {
         Iter {
             iter: self.storage.iter(),
         }
@@ -177,11 +182,15 @@ where
     /// let mut map = Set::new();
     /// map.insert(Key::One);
     /// assert_eq!(map.contains(Key::One), true);
+    /// assert_eq!(map.contains(&Key::One), true);
     /// assert_eq!(map.contains(Key::Two), false);
     /// ```
     #[inline]
-    pub fn contains(&self, key: K) -> bool {
-        self.storage.get(key).is_some()
+    pub fn contains<Q>(&self, key: Q) -> bool
+    where
+        Q: Borrow<K>,
+    {
+        self.storage.contains(key.borrow())
     }
 
     /// Adds a value to the set.
@@ -190,6 +199,13 @@ where
     ///
     /// If the set did have this value present, `false` is returned.
     ///
+    /// # Panics
+    ///
+    /// Panics if the storage of the value has a fixed capacity which is
+    /// already used up. See [`try_insert`] for a fallible alternative.
+    ///
+    /// [`try_insert`]: struct.Set.html#method.try_insert
+    ///
     /// # Examples
     ///
     /// ```
@@ -211,7 +227,46 @@ where
     /// ```
     #[inline]
     pub fn insert(&mut self, value: K) -> bool {
-        self.storage.insert(value, ()).is_none()
+        self.storage.insert(value)
+    }
+
+    /// Adds a value to the set, unless the storage of the value is full.
+    ///
+    /// This is [`insert`] for values stored with a fixed capacity, like
+    /// variants marked with `#[key(inline = N)]`, where [`insert`] panics when
+    /// there is no room for a new value. Instead, the value is handed back in
+    /// a [`CapacityError`]. Adding a value which is already present always
+    /// succeeds.
+    ///
+    /// [`insert`]: struct.Set.html#method.insert
+    /// [`CapacityError`]: map/struct.CapacityError.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     #[key(inline = 2)]
+    ///     Number(u32),
+    /// }
+    ///
+    /// let mut set = Set::new();
+    /// assert_eq!(set.try_insert(Key::Number(1)), Ok(true));
+    /// assert_eq!(set.try_insert(Key::Number(2)), Ok(true));
+    /// assert_eq!(set.try_insert(Key::Number(1)), Ok(false));
+    ///
+    /// let error = set.try_insert(Key::Number(3)).unwrap_err();
+    /// assert_eq!(error.key(), &Key::Number(3));
+    ///
+    /// assert_eq!(set.try_insert(Key::First), Ok(true));
+    /// assert_eq!(set.len(), 3);
+    /// ```
+    #[inline]
+    pub fn try_insert(&mut self, value: K) -> Result<bool, CapacityError<K, ()>> {
+        self.storage.try_insert(value)
     }
 
     /// Removes a value from the set. Returns `true` if the value was
@@ -234,8 +289,11 @@ where
     /// assert_eq!(set.remove(Key::One), false);
     /// ```
     #[inline]
-    pub fn remove(&mut self, key: K) -> bool {
-        self.storage.remove(key).is_some()
+    pub fn remove<Q>(&mut self, key: Q) -> bool
+    where
+        Q: Borrow<K>,
+    {
+        self.storage.remove(key.borrow())
     }
 
     /// Clears the set, removing all values.
@@ -261,6 +319,71 @@ where
         self.storage.clear()
     }
 
+    /// Clears the set, returning all values as an iterator.
+    ///
+    /// The set is left empty once this method returns, even if the
+    /// returned iterator is not consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut set = Set::new();
+    /// set.insert(Key::One);
+    /// set.insert(Key::Two);
+    ///
+    /// assert_eq!(set.drain().collect::<Vec<_>>(), vec![Key::One, Key::Two]);
+    /// assert!(set.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, K> {
+        Drain {
+            iter: mem::take(&mut self.storage).into_iter(),
+            marker: marker::PhantomData,
+        }
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `k` such that `f(k)` returns
+    /// `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut set = Set::new();
+    /// set.insert(Key::First);
+    /// set.insert(Key::Second);
+    /// set.insert(Key::Third);
+    ///
+    /// set.retain(|k| k != Key::Second);
+    ///
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![Key::First, Key::Third]);
+    /// ```
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(K) -> bool,
+    {
+        self.storage.retain(f);
+    }
+
     /// Returns true if the set contains no elements.
     ///
     /// # Examples
@@ -279,8 +402,9 @@ where
     /// set.insert(Key::One);
     /// assert!(!set.is_empty());
     /// ```
+    #[inline]
     pub fn is_empty(&self) -> bool {
-        self.storage.iter().next().is_none()
+        self.storage.is_empty()
     }
 
     /// Returns the number of elements in the set.
@@ -301,83 +425,975 @@ where
     /// set.insert(Key::One);
     /// assert_eq!(set.len(), 1);
     /// ```
+    #[inline]
     pub fn len(&self) -> usize {
-        self.storage.iter().count()
+        self.storage.len()
     }
-}
 
-impl<K> Clone for Set<K>
-where
-    K: Key<K, ()>,
-    K::Storage: Clone,
-{
-    fn clone(&self) -> Set<K> {
-        Set {
-            storage: self.storage.clone(),
+    /// Visits the values representing the union, i.e. all the values in
+    /// `self` or `other`, without duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// assert_eq!(
+    ///     a.union(&b).collect::<Vec<_>>(),
+    ///     vec![Key::One, Key::Two, Key::Three]
+    /// );
+    /// ```
+    #[inline]
+    pub fn union<'a>(&'a self, other: &'a Set<K>) -> Union<'a, K> {
+        Union {
+            iter: self.iter(),
+            other: other.iter(),
+            this: self,
         }
     }
-}
 
-impl<K> Default for Set<K>
-where
-    K: Key<K, ()>,
-{
-    fn default() -> Self {
-        Self::new()
+    /// Visits the values representing the intersection, i.e. the values
+    /// that are both in `self` and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// assert_eq!(a.intersection(&b).collect::<Vec<_>>(), vec![Key::Two]);
+    /// ```
+    #[inline]
+    pub fn intersection<'a>(&'a self, other: &'a Set<K>) -> Intersection<'a, K> {
+        Intersection {
+            iter: self.iter(),
+            other,
+        }
     }
-}
 
-impl<K> std::fmt::Debug for Set<K>
-where
-    K: Key<K, ()> + std::fmt::Debug,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let mut debug_set = f.debug_set();
-        for k in self.iter() {
-            debug_set.entry(&k);
+    /// Visits the values representing the difference, i.e. the values that
+    /// are in `self` but not in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// assert_eq!(a.difference(&b).collect::<Vec<_>>(), vec![Key::One]);
+    /// assert_eq!(b.difference(&a).collect::<Vec<_>>(), vec![Key::Three]);
+    /// ```
+    #[inline]
+    pub fn difference<'a>(&'a self, other: &'a Set<K>) -> Difference<'a, K> {
+        Difference {
+            iter: self.iter(),
+            other,
         }
-        debug_set.finish()
     }
-}
 
-impl<K> PartialEq for Set<K>
-where
-    K: Key<K, ()>,
-    K::Storage: PartialEq,
-{
-    fn eq(&self, other: &Self) -> bool {
-        self.storage == other.storage
+    /// Visits the values representing the symmetric difference, i.e. the
+    /// values that are in `self` or in `other` but not in both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// assert_eq!(
+    ///     a.symmetric_difference(&b).collect::<Vec<_>>(),
+    ///     vec![Key::One, Key::Three]
+    /// );
+    /// ```
+    #[inline]
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Set<K>) -> SymmetricDifference<'a, K> {
+        SymmetricDifference {
+            iter: self.difference(other),
+            other: other.difference(self),
+        }
     }
-}
 
-impl<K> Eq for Set<K>
-where
-    K: Key<K, ()>,
-    K::Storage: Eq,
-{
-}
+    /// Adds every value in `other` to `self`, so that `self` becomes the
+    /// union of both sets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a value has a fixed capacity which is too small
+    /// to hold the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// let mut c = a.clone();
+    /// c.union_with(&b);
+    /// assert_eq!(c.iter().collect::<Vec<_>>(), vec![Key::One, Key::Two, Key::Three]);
+    /// ```
+    #[inline]
+    pub fn union_with(&mut self, other: &Set<K>) {
+        for k in other.iter() {
+            self.storage.insert(k);
+        }
+    }
 
-/// An iterator over the items of a `Set`.
-///
-/// This `struct` is created by the [`iter`] method on [`Set`].
-/// See its documentation for more.
-///
-/// [`iter`]: struct.Set.html#method.iter
-/// [`Set`]: struct.Set.html
-pub struct Iter<K>
-where
-    K: Key<K, ()>,
-{
-    iter: <<K as Key<K, ()>>::Storage as Storage<K, ()>>::Iter,
-}
+    /// Removes every value from `self` which is not in `other`, so that
+    /// `self` becomes the intersection of both sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// let mut c = a.clone();
+    /// c.intersection_with(&b);
+    /// assert_eq!(c.iter().collect::<Vec<_>>(), vec![Key::Two]);
+    /// ```
+    #[inline]
+    pub fn intersection_with(&mut self, other: &Set<K>) {
+        self.storage.retain(|k| other.contains(k));
+    }
 
-impl<K> Iterator for Iter<K>
-where
-    K: Key<K, ()>,
-{
-    type Item = K;
+    /// Removes every value from `self` which is in `other`, so that `self`
+    /// becomes the difference of both sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// let mut c = a.clone();
+    /// c.difference_with(&b);
+    /// assert_eq!(c.iter().collect::<Vec<_>>(), vec![Key::One]);
+    /// ```
+    #[inline]
+    pub fn difference_with(&mut self, other: &Set<K>) {
+        self.storage.retain(|k| !other.contains(k));
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|(k, _)| k)
+    /// Toggles every value in `other` in `self`, so that `self` becomes the
+    /// symmetric difference of both sets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a value has a fixed capacity which is too small
+    /// to hold the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// let mut c = a.clone();
+    /// c.symmetric_difference_with(&b);
+    /// assert_eq!(c.iter().collect::<Vec<_>>(), vec![Key::One, Key::Three]);
+    /// ```
+    #[inline]
+    pub fn symmetric_difference_with(&mut self, other: &Set<K>) {
+        for k in other.iter() {
+            if !self.storage.remove(&k) {
+                self.storage.insert(k);
+            }
+        }
+    }
+
+    /// Returns `true` if every value in `self` is also in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let sup: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let mut set = Set::new();
+    ///
+    /// assert!(set.is_subset(&sup));
+    /// set.insert(Key::One);
+    /// assert!(set.is_subset(&sup));
+    /// assert!(!sup.is_subset(&set));
+    /// ```
+    #[inline]
+    pub fn is_subset(&self, other: &Set<K>) -> bool {
+        self.len() <= other.len() && self.iter().all(|k| other.contains(k))
+    }
+
+    /// Returns `true` if every value in `other` is also in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let sub: Set<_> = vec![Key::One].into_iter().collect();
+    /// let mut set = Set::new();
+    ///
+    /// assert!(!set.is_superset(&sub));
+    /// set.insert(Key::One);
+    /// set.insert(Key::Two);
+    /// assert!(set.is_superset(&sub));
+    /// ```
+    #[inline]
+    pub fn is_superset(&self, other: &Set<K>) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if `self` has no values in common with `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One].into_iter().collect();
+    /// let mut b = Set::new();
+    ///
+    /// assert!(a.is_disjoint(&b));
+    /// b.insert(Key::Two);
+    /// assert!(a.is_disjoint(&b));
+    /// b.insert(Key::One);
+    /// assert!(!a.is_disjoint(&b));
+    /// ```
+    #[inline]
+    pub fn is_disjoint(&self, other: &Set<K>) -> bool {
+        self.iter().all(|k| !other.contains(k))
+    }
+}
+
+impl<K> Clone for Set<K>
+where
+    K: Key<K, ()>,
+    K::SetStorage: Clone,
+{
+    fn clone(&self) -> Set<K> {
+        Set {
+            storage: self.storage.clone(),
+        }
+    }
+}
+
+impl<K> Default for Set<K>
+where
+    K: Key<K, ()>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> core::fmt::Debug for Set<K>
+where
+    K: Key<K, ()> + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut debug_set = f.debug_set();
+        for k in self.iter() {
+            debug_set.entry(&k);
+        }
+        debug_set.finish()
+    }
+}
+
+impl<K> PartialEq for Set<K>
+where
+    K: Key<K, ()>,
+    K::SetStorage: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.storage == other.storage
+    }
+}
+
+impl<K> Eq for Set<K>
+where
+    K: Key<K, ()>,
+    K::SetStorage: Eq,
+{
+}
+
+impl<K> BitOr<&Set<K>> for &Set<K>
+where
+    K: Key<K, ()>,
+{
+    type Output = Set<K>;
+
+    /// Returns the union of `self` and `rhs` as a new `Set<K>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a value has a fixed capacity which is too small
+    /// to hold the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// let set = &a | &b;
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![Key::One, Key::Two, Key::Three]);
+    /// ```
+    #[inline]
+    fn bitor(self, rhs: &Set<K>) -> Set<K> {
+        self.union(rhs).collect()
+    }
+}
+
+impl<'a, K> BitOrAssign<&'a Set<K>> for Set<K>
+where
+    K: Key<K, ()>,
+{
+    /// Modifies `self` in place to be the union of `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a value has a fixed capacity which is too small
+    /// to hold the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let mut a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// a |= &b;
+    /// assert_eq!(a.iter().collect::<Vec<_>>(), vec![Key::One, Key::Two, Key::Three]);
+    /// ```
+    #[inline]
+    fn bitor_assign(&mut self, rhs: &'a Set<K>) {
+        self.union_with(rhs);
+    }
+}
+
+impl<K> BitAnd<&Set<K>> for &Set<K>
+where
+    K: Key<K, ()>,
+{
+    type Output = Set<K>;
+
+    /// Returns the intersection of `self` and `rhs` as a new `Set<K>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// let set = &a & &b;
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![Key::Two]);
+    /// ```
+    #[inline]
+    fn bitand(self, rhs: &Set<K>) -> Set<K> {
+        self.intersection(rhs).collect()
+    }
+}
+
+impl<'a, K> BitAndAssign<&'a Set<K>> for Set<K>
+where
+    K: Key<K, ()>,
+{
+    /// Modifies `self` in place to be the intersection of `self` and `rhs`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let mut a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// a &= &b;
+    /// assert_eq!(a.iter().collect::<Vec<_>>(), vec![Key::Two]);
+    /// ```
+    #[inline]
+    fn bitand_assign(&mut self, rhs: &'a Set<K>) {
+        self.intersection_with(rhs);
+    }
+}
+
+impl<K> Sub<&Set<K>> for &Set<K>
+where
+    K: Key<K, ()>,
+{
+    type Output = Set<K>;
+
+    /// Returns the difference of `self` and `rhs` as a new `Set<K>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// let set = &a - &b;
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![Key::One]);
+    /// ```
+    #[inline]
+    fn sub(self, rhs: &Set<K>) -> Set<K> {
+        self.difference(rhs).collect()
+    }
+}
+
+impl<'a, K> SubAssign<&'a Set<K>> for Set<K>
+where
+    K: Key<K, ()>,
+{
+    /// Modifies `self` in place to be the difference of `self` and `rhs`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let mut a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// a -= &b;
+    /// assert_eq!(a.iter().collect::<Vec<_>>(), vec![Key::One]);
+    /// ```
+    #[inline]
+    fn sub_assign(&mut self, rhs: &'a Set<K>) {
+        self.difference_with(rhs);
+    }
+}
+
+impl<K> BitXor<&Set<K>> for &Set<K>
+where
+    K: Key<K, ()>,
+{
+    type Output = Set<K>;
+
+    /// Returns the symmetric difference of `self` and `rhs` as a new `Set<K>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a value has a fixed capacity which is too small
+    /// to hold the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// let set = &a ^ &b;
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![Key::One, Key::Three]);
+    /// ```
+    #[inline]
+    fn bitxor(self, rhs: &Set<K>) -> Set<K> {
+        self.symmetric_difference(rhs).collect()
+    }
+}
+
+impl<'a, K> BitXorAssign<&'a Set<K>> for Set<K>
+where
+    K: Key<K, ()>,
+{
+    /// Modifies `self` in place to be the symmetric difference of `self` and `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a value has a fixed capacity which is too small
+    /// to hold the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let mut a: Set<_> = vec![Key::One, Key::Two].into_iter().collect();
+    /// let b: Set<_> = vec![Key::Two, Key::Three].into_iter().collect();
+    ///
+    /// a ^= &b;
+    /// assert_eq!(a.iter().collect::<Vec<_>>(), vec![Key::One, Key::Three]);
+    /// ```
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: &'a Set<K>) {
+        self.symmetric_difference_with(rhs);
+    }
+}
+
+impl<K> iter::FromIterator<K> for Set<K>
+where
+    K: Key<K, ()>,
+{
+    /// Creates a `Set` from an iterator of values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a value has a fixed capacity which is already
+    /// used up. See [`Set::try_insert`] for a fallible alternative.
+    ///
+    /// [`Set::try_insert`]: struct.Set.html#method.try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let set: Set<_> = vec![Key::One, Key::Three, Key::One].into_iter().collect();
+    ///
+    /// assert!(set.contains(Key::One));
+    /// assert!(!set.contains(Key::Two));
+    /// assert!(set.contains(Key::Three));
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    #[inline]
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = K>,
+    {
+        let mut set = Set::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K> Extend<K> for Set<K>
+where
+    K: Key<K, ()>,
+{
+    /// Inserts all values from the iterator into the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage of a value has a fixed capacity which is already
+    /// used up. See [`Set::try_insert`] for a fallible alternative.
+    ///
+    /// [`Set::try_insert`]: struct.Set.html#method.try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut set = Set::new();
+    /// set.extend(vec![Key::One, Key::Two]);
+    ///
+    /// assert!(set.contains(Key::One));
+    /// assert!(set.contains(Key::Two));
+    /// ```
+    #[inline]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = K>,
+    {
+        for k in iter {
+            self.storage.insert(k);
+        }
+    }
+}
+
+impl<K> IntoIterator for Set<K>
+where
+    K: Key<K, ()>,
+{
+    type Item = K;
+    type IntoIter = IntoIter<K>;
+
+    /// Creates a consuming iterator visiting all the values in arbitrary
+    /// order. The set cannot be used after calling this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let mut set = Set::new();
+    /// set.insert(Key::One);
+    /// set.insert(Key::Three);
+    ///
+    /// let vec: Vec<Key> = set.into_iter().collect();
+    /// assert_eq!(vec, vec![Key::One, Key::Three]);
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: self.storage.into_iter(),
+        }
+    }
+}
+
+impl<'a, K: 'a> IntoIterator for &'a Set<K>
+where
+    K: Key<K, ()>,
+{
+    type Item = K;
+    type IntoIter = Iter<'a, K>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the items of a `Set`.
+///
+/// This `struct` is created by the [`iter`] method on [`Set`].
+/// See its documentation for more.
+///
+/// [`iter`]: struct.Set.html#method.iter
+/// [`Set`]: struct.Set.html
+pub struct Iter<'a, K: 'a>
+where
+    K: Key<K, ()>,
+{
+    iter: <K::SetStorage as SetStorage<K>>::Iter<'a>,
+}
+
+impl<'a, K: 'a> Iterator for Iter<'a, K>
+where
+    K: Key<K, ()>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// An owning iterator over the items of a `Set`.
+///
+/// This `struct` is created by the [`into_iter`] method on [`Set`]
+/// (provided by the `IntoIterator` trait). See its documentation for more.
+///
+/// [`into_iter`]: struct.Set.html#method.into_iter
+/// [`Set`]: struct.Set.html
+pub struct IntoIter<K>
+where
+    K: Key<K, ()>,
+{
+    iter: <K::SetStorage as SetStorage<K>>::IntoIter,
+}
+
+impl<K> Iterator for IntoIter<K>
+where
+    K: Key<K, ()>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// A draining iterator over the items of a `Set`.
+///
+/// This `struct` is created by the [`drain`] method on [`Set`].
+/// See its documentation for more.
+///
+/// [`drain`]: struct.Set.html#method.drain
+/// [`Set`]: struct.Set.html
+pub struct Drain<'a, K>
+where
+    K: Key<K, ()>,
+{
+    iter: <K::SetStorage as SetStorage<K>>::IntoIter,
+    marker: marker::PhantomData<&'a mut ()>,
+}
+
+impl<'a, K> Iterator for Drain<'a, K>
+where
+    K: Key<K, ()>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// A lazy iterator producing the union of two `Set`s.
+///
+/// This `struct` is created by the [`union`] method on [`Set`].
+/// See its documentation for more.
+///
+/// [`union`]: struct.Set.html#method.union
+/// [`Set`]: struct.Set.html
+pub struct Union<'a, K: 'a>
+where
+    K: Key<K, ()>,
+{
+    iter: Iter<'a, K>,
+    other: Iter<'a, K>,
+    this: &'a Set<K>,
+}
+
+impl<'a, K: 'a> Iterator for Union<'a, K>
+where
+    K: Key<K, ()>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(k) = self.iter.next() {
+            return Some(k);
+        }
+
+        let this = self.this;
+        self.other.by_ref().find(|k| !this.contains(k))
+    }
+}
+
+/// A lazy iterator producing the intersection of two `Set`s.
+///
+/// This `struct` is created by the [`intersection`] method on [`Set`].
+/// See its documentation for more.
+///
+/// [`intersection`]: struct.Set.html#method.intersection
+/// [`Set`]: struct.Set.html
+pub struct Intersection<'a, K: 'a>
+where
+    K: Key<K, ()>,
+{
+    iter: Iter<'a, K>,
+    other: &'a Set<K>,
+}
+
+impl<'a, K: 'a> Iterator for Intersection<'a, K>
+where
+    K: Key<K, ()>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.by_ref().find(|k| other.contains(k))
+    }
+}
+
+/// A lazy iterator producing the difference of two `Set`s.
+///
+/// This `struct` is created by the [`difference`] method on [`Set`].
+/// See its documentation for more.
+///
+/// [`difference`]: struct.Set.html#method.difference
+/// [`Set`]: struct.Set.html
+pub struct Difference<'a, K: 'a>
+where
+    K: Key<K, ()>,
+{
+    iter: Iter<'a, K>,
+    other: &'a Set<K>,
+}
+
+impl<'a, K: 'a> Iterator for Difference<'a, K>
+where
+    K: Key<K, ()>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.by_ref().find(|k| !other.contains(k))
+    }
+}
+
+/// A lazy iterator producing the symmetric difference of two `Set`s.
+///
+/// This `struct` is created by the [`symmetric_difference`] method on [`Set`].
+/// See its documentation for more.
+///
+/// [`symmetric_difference`]: struct.Set.html#method.symmetric_difference
+/// [`Set`]: struct.Set.html
+pub struct SymmetricDifference<'a, K: 'a>
+where
+    K: Key<K, ()>,
+{
+    iter: Difference<'a, K>,
+    other: Difference<'a, K>,
+}
+
+impl<'a, K: 'a> Iterator for SymmetricDifference<'a, K>
+where
+    K: Key<K, ()>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some(k) => Some(k),
+            None => self.other.next(),
+        }
     }
 }
@@ -1,14 +1,40 @@
 //! Module for the trait to define `Storage`.
 
+mod bitset;
 mod boolean;
+mod byte;
+#[cfg(feature = "alloc")]
+mod dense;
+mod inline;
+#[cfg(feature = "alloc")]
 mod map;
 mod option;
+mod ordering;
+mod result;
+mod set;
 mod singleton;
+mod total;
+mod tuple;
 
+pub use self::bitset::Bits;
 pub use self::boolean::BooleanStorage;
+pub use self::byte::ByteStorage;
+#[cfg(feature = "alloc")]
+pub use self::dense::DenseStorage;
+pub use self::inline::InlineMapStorage;
+#[cfg(feature = "alloc")]
 pub use self::map::MapStorage;
 pub use self::option::OptionStorage;
+pub use self::ordering::OrderingStorage;
+pub use self::result::ResultStorage;
+pub use self::set::SetStorage;
 pub use self::singleton::SingletonStorage;
+pub use self::total::{
+    BooleanTotalStorage, OptionTotalStorage, SingletonTotalStorage, TotalStorage,
+};
+pub use self::tuple::TupleStorage;
+
+use crate::map::{CapacityError, Entry, OccupiedEntry, VacantEntry};
 
 /// The trait defining how storage works.
 ///
@@ -18,31 +44,75 @@ pub use self::singleton::SingletonStorage;
 /// - `V` is the value being stored.
 pub trait Storage<K, V>: Default {
     /// Immutable iterator over storage.
-    /// Uses raw pointers (unsafe) since we don't have GATs.
-    type Iter: Clone + Iterator<Item = (K, *const V)>;
+    type Iter<'this>: Clone + Iterator<Item = (K, &'this V)>
+    where
+        Self: 'this,
+        V: 'this;
 
     /// Mutable iterator over storage.
-    /// Uses raw pointers (unsafe) since we don't have GATs.
-    type IterMut: Iterator<Item = (K, *mut V)>;
+    type IterMut<'this>: Iterator<Item = (K, &'this mut V)>
+    where
+        Self: 'this,
+        V: 'this;
+
+    /// Owning iterator over storage.
+    type IntoIter: Iterator<Item = (K, V)>;
+
+    /// The occupied entry produced by [`Storage::entry`].
+    type Occupied<'this>: OccupiedEntry<'this, K, V>
+    where
+        Self: 'this;
+
+    /// The vacant entry produced by [`Storage::entry`].
+    type Vacant<'this>: VacantEntry<'this, K, V>
+    where
+        Self: 'this;
+
+    /// This is the storage abstraction for [`Map::len`](struct.Map.html#method.len).
+    fn len(&self) -> usize;
+
+    /// This is the storage abstraction for [`Map::is_empty`](struct.Map.html#method.is_empty).
+    fn is_empty(&self) -> bool;
 
     /// This is the storage abstraction for [`Map::insert`](struct.Map.html#method.insert).
     fn insert(&mut self, key: K, value: V) -> Option<V>;
 
+    /// This is the storage abstraction for [`Map::try_insert`](struct.Map.html#method.try_insert).
+    ///
+    /// Storage with a fixed capacity hands back the key and value when it is
+    /// full. The default implementation defers to [`Storage::insert`], for
+    /// storage which always has room.
+    #[inline]
+    fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<K, V>> {
+        Ok(self.insert(key, value))
+    }
+
     /// This is the storage abstraction for [`Map::get`](struct.Map.html#method.get).
-    fn get(&self, key: K) -> Option<&V>;
+    fn get(&self, key: &K) -> Option<&V>;
 
     /// This is the storage abstraction for [`Map::get_mut`](struct.Map.html#method.get_mut).
-    fn get_mut(&mut self, key: K) -> Option<&mut V>;
+    fn get_mut(&mut self, key: &K) -> Option<&mut V>;
 
     /// This is the storage abstraction for [`Map::remove`](struct.Map.html#method.remove).
-    fn remove(&mut self, key: K) -> Option<V>;
+    fn remove(&mut self, key: &K) -> Option<V>;
 
     /// This is the storage abstraction for [`Map::clear`](struct.Map.html#method.clear).
     fn clear(&mut self);
 
+    /// This is the storage abstraction for [`Map::retain`](struct.Map.html#method.retain).
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(K, &mut V) -> bool;
+
     /// This is the storage abstraction for [`Map::iter`](struct.Map.html#method.iter).
-    fn iter(&self) -> Self::Iter;
+    fn iter(&self) -> Self::Iter<'_>;
 
     /// This is the storage abstraction for [`Map::iter_mut`](struct.Map.html#method.iter_mut).
-    fn iter_mut(&mut self) -> Self::IterMut;
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+
+    /// This is the storage abstraction for [`Map::into_iter`](struct.Map.html#method.into_iter).
+    fn into_iter(self) -> Self::IntoIter;
+
+    /// This is the storage abstraction for [`Map::entry`](struct.Map.html#method.entry).
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V>;
 }
@@ -0,0 +1,126 @@
+/// The integer types which back the set storage generated by `#[key(bitset)]`.
+///
+/// Each key is assigned a bit index by the `Key` derive, and every set
+/// operation is a single bit operation on the backing integer.
+///
+/// This is implemented for the unsigned integers `u8` through `u128`, and for
+/// `[u64; N]` to support enums with more than 128 variants.
+pub trait Bits: Copy + Eq {
+    /// Bits where no index is set.
+    const EMPTY: Self;
+
+    /// Test if the bit at the given index is set.
+    fn test(&self, index: u32) -> bool;
+
+    /// Set the bit at the given index, returning `true` if it was not already
+    /// set.
+    fn set(&mut self, index: u32) -> bool;
+
+    /// Unset the bit at the given index, returning `true` if it was set.
+    fn unset(&mut self, index: u32) -> bool;
+
+    /// Count the number of bits set.
+    fn count(&self) -> usize;
+
+    /// Test if no bits are set.
+    fn is_empty(&self) -> bool;
+
+    /// Unset the lowest bit which is set and return its index.
+    fn pop_lowest(&mut self) -> Option<u32>;
+}
+
+macro_rules! impl_bits {
+    ($ty:ty) => {
+        impl Bits for $ty {
+            const EMPTY: Self = 0;
+
+            #[inline]
+            fn test(&self, index: u32) -> bool {
+                *self & (1 << index) != 0
+            }
+
+            #[inline]
+            fn set(&mut self, index: u32) -> bool {
+                let bit = 1 << index;
+                let unset = *self & bit == 0;
+                *self |= bit;
+                unset
+            }
+
+            #[inline]
+            fn unset(&mut self, index: u32) -> bool {
+                let bit = 1 << index;
+                let set = *self & bit != 0;
+                *self &= !bit;
+                set
+            }
+
+            #[inline]
+            fn count(&self) -> usize {
+                self.count_ones() as usize
+            }
+
+            #[inline]
+            fn is_empty(&self) -> bool {
+                *self == 0
+            }
+
+            #[inline]
+            fn pop_lowest(&mut self) -> Option<u32> {
+                if *self == 0 {
+                    return None;
+                }
+
+                let index = self.trailing_zeros();
+                *self &= *self - 1;
+                Some(index)
+            }
+        }
+    };
+}
+
+impl_bits!(u8);
+impl_bits!(u16);
+impl_bits!(u32);
+impl_bits!(u64);
+impl_bits!(u128);
+
+impl<const N: usize> Bits for [u64; N] {
+    const EMPTY: Self = [0; N];
+
+    #[inline]
+    fn test(&self, index: u32) -> bool {
+        self[(index / 64) as usize].test(index % 64)
+    }
+
+    #[inline]
+    fn set(&mut self, index: u32) -> bool {
+        self[(index / 64) as usize].set(index % 64)
+    }
+
+    #[inline]
+    fn unset(&mut self, index: u32) -> bool {
+        self[(index / 64) as usize].unset(index % 64)
+    }
+
+    #[inline]
+    fn count(&self) -> usize {
+        self.iter().map(Bits::count).sum()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.iter().all(Bits::is_empty)
+    }
+
+    #[inline]
+    fn pop_lowest(&mut self) -> Option<u32> {
+        for (n, word) in self.iter_mut().enumerate() {
+            if let Some(index) = word.pop_lowest() {
+                return Some(n as u32 * 64 + index);
+            }
+        }
+
+        None
+    }
+}
@@ -1,5 +1,6 @@
+use crate::map::{Entry, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
 use crate::storage::Storage;
-use std::mem;
 
 /// Storage for `bool`s.
 pub struct BooleanStorage<V> {
@@ -39,22 +40,22 @@ where
 
 impl<V> Eq for BooleanStorage<V> where V: Eq {}
 
-pub struct Iter<V> {
-    t: Option<*const V>,
-    f: Option<*const V>,
+pub struct Iter<'a, V> {
+    t: Option<&'a V>,
+    f: Option<&'a V>,
 }
 
-impl<V> Clone for Iter<V> {
-    fn clone(&self) -> Iter<V> {
+impl<'a, V> Clone for Iter<'a, V> {
+    fn clone(&self) -> Iter<'a, V> {
         Iter {
-            t: self.t.clone(),
-            f: self.f.clone(),
+            t: self.t,
+            f: self.f,
         }
     }
 }
 
-impl<V> Iterator for Iter<V> {
-    type Item = (bool, *const V);
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (bool, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(t) = self.t.take() {
@@ -69,13 +70,13 @@ impl<V> Iterator for Iter<V> {
     }
 }
 
-pub struct IterMut<V> {
-    t: Option<*mut V>,
-    f: Option<*mut V>,
+pub struct IterMut<'a, V> {
+    t: Option<&'a mut V>,
+    f: Option<&'a mut V>,
 }
 
-impl<V> Iterator for IterMut<V> {
-    type Item = (bool, *mut V);
+impl<'a, V> Iterator for IterMut<'a, V> {
+    type Item = (bool, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(t) = self.t.take() {
@@ -90,39 +91,141 @@ impl<V> Iterator for IterMut<V> {
     }
 }
 
+pub struct IntoIter<V> {
+    t: Option<V>,
+    f: Option<V>,
+}
+
+impl<V> Iterator for IntoIter<V> {
+    type Item = (bool, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(t) = self.t.take() {
+            return Some((true, t));
+        }
+
+        if let Some(f) = self.f.take() {
+            return Some((false, f));
+        }
+
+        None
+    }
+}
+
+pub struct Occupied<'a, V> {
+    key: bool,
+    inner: SomeBucket<'a, V>,
+}
+
+impl<'a, V> OccupiedEntry<'a, bool, V> for Occupied<'a, V> {
+    #[inline]
+    fn key(&self) -> bool {
+        self.key
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.replace(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.inner.take()
+    }
+}
+
+pub struct Vacant<'a, V> {
+    key: bool,
+    inner: NoneBucket<'a, V>,
+}
+
+impl<'a, V> VacantEntry<'a, bool, V> for Vacant<'a, V> {
+    #[inline]
+    fn key(&self) -> bool {
+        self.key
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.inner.insert(value)
+    }
+}
+
 impl<V> Storage<bool, V> for BooleanStorage<V> {
-    type Iter = Iter<V>;
-    type IterMut = IterMut<V>;
+    type Iter<'this>
+        = Iter<'this, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<V>;
+    type Occupied<'this>
+        = Occupied<'this, V>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        usize::from(self.t.is_some()) + usize::from(self.f.is_some())
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.t.is_none() && self.f.is_none()
+    }
 
     #[inline]
     fn insert(&mut self, key: bool, value: V) -> Option<V> {
         match key {
-            true => mem::replace(&mut self.t, Some(value)),
-            false => mem::replace(&mut self.f, Some(value)),
+            true => self.t.replace(value),
+            false => self.f.replace(value),
         }
     }
 
     #[inline]
-    fn get(&self, key: bool) -> Option<&V> {
-        match key {
+    fn get(&self, key: &bool) -> Option<&V> {
+        match *key {
             true => self.t.as_ref(),
             false => self.f.as_ref(),
         }
     }
 
     #[inline]
-    fn get_mut(&mut self, key: bool) -> Option<&mut V> {
-        match key {
+    fn get_mut(&mut self, key: &bool) -> Option<&mut V> {
+        match *key {
             true => self.t.as_mut(),
             false => self.f.as_mut(),
         }
     }
 
     #[inline]
-    fn remove(&mut self, key: bool) -> Option<V> {
-        match key {
-            true => mem::replace(&mut self.t, None),
-            false => mem::replace(&mut self.f, None),
+    fn remove(&mut self, key: &bool) -> Option<V> {
+        match *key {
+            true => self.t.take(),
+            false => self.f.take(),
         }
     }
 
@@ -133,18 +236,57 @@ impl<V> Storage<bool, V> for BooleanStorage<V> {
     }
 
     #[inline]
-    fn iter(&self) -> Self::Iter {
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(bool, &mut V) -> bool,
+    {
+        if let Some(t) = self.t.as_mut() {
+            if !func(true, t) {
+                self.t = None;
+            }
+        }
+
+        if let Some(f) = self.f.as_mut() {
+            if !func(false, f) {
+                self.f = None;
+            }
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
         Iter {
-            t: self.t.as_ref().map(|v| v as *const V),
-            f: self.f.as_ref().map(|v| v as *const V),
+            t: self.t.as_ref(),
+            f: self.f.as_ref(),
         }
     }
 
     #[inline]
-    fn iter_mut(&mut self) -> Self::IterMut {
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
         IterMut {
-            t: self.t.as_mut().map(|v| v as *mut V),
-            f: self.f.as_mut().map(|v| v as *mut V),
+            t: self.t.as_mut(),
+            f: self.f.as_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            t: self.t,
+            f: self.f,
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, key: bool) -> Entry<'_, Self, bool, V> {
+        let slot = match key {
+            true => &mut self.t,
+            false => &mut self.f,
+        };
+
+        match OptionBucket::new(slot) {
+            OptionBucket::Some(inner) => Entry::Occupied(Occupied { key, inner }),
+            OptionBucket::None(inner) => Entry::Vacant(Vacant { key, inner }),
         }
     }
 }
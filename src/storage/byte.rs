@@ -0,0 +1,332 @@
+use crate::key::Enumerable;
+use crate::map::{Entry, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
+use crate::storage::Storage;
+use core::array;
+use core::iter;
+use core::marker;
+use core::slice;
+
+/// The number of slots in a [`ByteStorage`].
+const SLOTS: usize = 256;
+
+/// Storage for keys with at most 256 values, like `u8` and `i8`, which are
+/// stored inline in a slot per key indexed by the [`ordinal`] of the key.
+///
+/// Every operation is a direct index into the slots without any hashing or
+/// allocation, at the cost of the storage always having room for every key.
+///
+/// # Panics
+///
+/// Inserting a key whose ordinal is 256 or above panics, so this must only
+/// be used as the storage of keys with at most 256 values.
+///
+/// [`ordinal`]: ../key/trait.Enumerable.html#tymethod.ordinal
+pub struct ByteStorage<K, V> {
+    entries: [Option<V>; SLOTS],
+    len: usize,
+    key: marker::PhantomData<K>,
+}
+
+impl<K, V> Clone for ByteStorage<K, V>
+where
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        ByteStorage {
+            entries: self.entries.clone(),
+            len: self.len,
+            key: marker::PhantomData,
+        }
+    }
+}
+
+impl<K, V> Default for ByteStorage<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: array::from_fn(|_| None),
+            len: 0,
+            key: marker::PhantomData,
+        }
+    }
+}
+
+impl<K, V> PartialEq for ByteStorage<K, V>
+where
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.entries == other.entries
+    }
+}
+
+impl<K, V> Eq for ByteStorage<K, V> where V: Eq {}
+
+pub struct Iter<'a, K, V> {
+    iter: iter::Enumerate<slice::Iter<'a, Option<V>>>,
+    key: marker::PhantomData<K>,
+}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    fn clone(&self) -> Iter<'a, K, V> {
+        Iter {
+            iter: self.iter.clone(),
+            key: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Enumerable,
+{
+    type Item = (K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, entry) in &mut self.iter {
+            if let Some(value) = entry {
+                return Some((K::from_ordinal(index)?, value));
+            }
+        }
+
+        None
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    iter: iter::Enumerate<slice::IterMut<'a, Option<V>>>,
+    key: marker::PhantomData<K>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V>
+where
+    K: Enumerable,
+{
+    type Item = (K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, entry) in &mut self.iter {
+            if let Some(value) = entry {
+                return Some((K::from_ordinal(index)?, value));
+            }
+        }
+
+        None
+    }
+}
+
+pub struct IntoIter<K, V> {
+    iter: iter::Enumerate<array::IntoIter<Option<V>, SLOTS>>,
+    key: marker::PhantomData<K>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V>
+where
+    K: Enumerable,
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, entry) in &mut self.iter {
+            if let Some(value) = entry {
+                return Some((K::from_ordinal(index)?, value));
+            }
+        }
+
+        None
+    }
+}
+
+pub struct Occupied<'a, K, V> {
+    key: K,
+    bucket: SomeBucket<'a, V>,
+    len: &'a mut usize,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
+where
+    K: Copy,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.bucket.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.bucket.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.bucket.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.bucket.replace(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        *self.len -= 1;
+        self.bucket.take()
+    }
+}
+
+pub struct Vacant<'a, K, V> {
+    key: K,
+    bucket: NoneBucket<'a, V>,
+    len: &'a mut usize,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
+where
+    K: Copy,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        *self.len += 1;
+        self.bucket.insert(value)
+    }
+}
+
+impl<K, V> Storage<K, V> for ByteStorage<K, V>
+where
+    K: Enumerable,
+{
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<K, V>;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let existing = self.entries[key.ordinal()].replace(value);
+
+        if existing.is_none() {
+            self.len += 1;
+        }
+
+        existing
+    }
+
+    #[inline]
+    fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key.ordinal())?.as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.entries.get_mut(key.ordinal())?.as_mut()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.entries.get_mut(key.ordinal())?.take()?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        for entry in &mut self.entries {
+            *entry = None;
+        }
+
+        self.len = 0;
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            if let (Some(key), Some(value)) = (K::from_ordinal(index), entry.as_mut()) {
+                if !func(key, value) {
+                    *entry = None;
+                    self.len -= 1;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            iter: self.entries.iter().enumerate(),
+            key: marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            iter: self.entries.iter_mut().enumerate(),
+            key: marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: IntoIterator::into_iter(self.entries).enumerate(),
+            key: marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        match OptionBucket::new(&mut self.entries[key.ordinal()]) {
+            OptionBucket::Some(bucket) => Entry::Occupied(Occupied {
+                key,
+                bucket,
+                len: &mut self.len,
+            }),
+            OptionBucket::None(bucket) => Entry::Vacant(Vacant {
+                key,
+                bucket,
+                len: &mut self.len,
+            }),
+        }
+    }
+}
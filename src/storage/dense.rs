@@ -0,0 +1,370 @@
+use crate::key::Enumerable;
+use crate::map::{Entry, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
+use crate::storage::Storage;
+use alloc::vec::{self, Vec};
+use core::iter;
+use core::marker;
+use core::slice;
+
+/// Storage for keys with a large but bounded number of values, like
+/// [`Dense<u16>`], which are stored densely in a slot per key indexed by the
+/// [`ordinal`] of the key.
+///
+/// The slots are allocated when the first value is inserted, after which
+/// every operation is a direct index into them without any hashing.
+///
+/// [`Dense<u16>`]: ../key/struct.Dense.html
+/// [`ordinal`]: ../key/trait.Enumerable.html#tymethod.ordinal
+pub struct DenseStorage<K, V> {
+    entries: Vec<Option<V>>,
+    len: usize,
+    key: marker::PhantomData<K>,
+}
+
+impl<K, V> DenseStorage<K, V>
+where
+    K: Enumerable,
+{
+    /// Access the slot of the given key, allocating the slots if needed.
+    fn slot(entries: &mut Vec<Option<V>>, key: K) -> &mut Option<V> {
+        if entries.is_empty() {
+            entries.resize_with(K::COUNT, || None);
+        }
+
+        &mut entries[key.ordinal()]
+    }
+}
+
+impl<K, V> Clone for DenseStorage<K, V>
+where
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        DenseStorage {
+            entries: self.entries.clone(),
+            len: self.len,
+            key: marker::PhantomData,
+        }
+    }
+}
+
+impl<K, V> Default for DenseStorage<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            len: 0,
+            key: marker::PhantomData,
+        }
+    }
+}
+
+impl<K, V> PartialEq for DenseStorage<K, V>
+where
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        // Since both storages hold the same number of values, every value in
+        // one of them being present in the other means that they are equal.
+        self.entries
+            .iter()
+            .enumerate()
+            .all(|(index, entry)| match entry {
+                Some(value) => other.entries.get(index).and_then(Option::as_ref) == Some(value),
+                None => true,
+            })
+    }
+}
+
+impl<K, V> Eq for DenseStorage<K, V> where V: Eq {}
+
+pub struct Iter<'a, K, V> {
+    iter: iter::Enumerate<slice::Iter<'a, Option<V>>>,
+    key: marker::PhantomData<K>,
+}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    fn clone(&self) -> Iter<'a, K, V> {
+        Iter {
+            iter: self.iter.clone(),
+            key: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Enumerable,
+{
+    type Item = (K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, entry) in &mut self.iter {
+            if let Some(value) = entry {
+                return Some((K::from_ordinal(index)?, value));
+            }
+        }
+
+        None
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    iter: iter::Enumerate<slice::IterMut<'a, Option<V>>>,
+    key: marker::PhantomData<K>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V>
+where
+    K: Enumerable,
+{
+    type Item = (K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, entry) in &mut self.iter {
+            if let Some(value) = entry {
+                return Some((K::from_ordinal(index)?, value));
+            }
+        }
+
+        None
+    }
+}
+
+pub struct IntoIter<K, V> {
+    iter: iter::Enumerate<vec::IntoIter<Option<V>>>,
+    key: marker::PhantomData<K>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V>
+where
+    K: Enumerable,
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, entry) in &mut self.iter {
+            if let Some(value) = entry {
+                return Some((K::from_ordinal(index)?, value));
+            }
+        }
+
+        None
+    }
+}
+
+pub struct Occupied<'a, K, V> {
+    key: K,
+    bucket: SomeBucket<'a, V>,
+    len: &'a mut usize,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
+where
+    K: Copy,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.bucket.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.bucket.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.bucket.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.bucket.replace(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        *self.len -= 1;
+        self.bucket.take()
+    }
+}
+
+/// The slot of a vacant entry, which is only known once the slots are
+/// allocated.
+enum VacantSlot<'a, V> {
+    Unallocated(&'a mut Vec<Option<V>>),
+    Allocated(NoneBucket<'a, V>),
+}
+
+pub struct Vacant<'a, K, V> {
+    key: K,
+    slot: VacantSlot<'a, V>,
+    len: &'a mut usize,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
+where
+    K: Enumerable,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        *self.len += 1;
+
+        match self.slot {
+            VacantSlot::Unallocated(entries) => {
+                DenseStorage::<K, V>::slot(entries, self.key).insert(value)
+            }
+            VacantSlot::Allocated(bucket) => bucket.insert(value),
+        }
+    }
+}
+
+impl<K, V> Storage<K, V> for DenseStorage<K, V>
+where
+    K: Enumerable,
+{
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<K, V>;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let existing = Self::slot(&mut self.entries, key).replace(value);
+
+        if existing.is_none() {
+            self.len += 1;
+        }
+
+        existing
+    }
+
+    #[inline]
+    fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key.ordinal())?.as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.entries.get_mut(key.ordinal())?.as_mut()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.entries.get_mut(key.ordinal())?.take()?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.len = 0;
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            if let (Some(key), Some(value)) = (K::from_ordinal(index), entry.as_mut()) {
+                if !func(key, value) {
+                    *entry = None;
+                    self.len -= 1;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            iter: self.entries.iter().enumerate(),
+            key: marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            iter: self.entries.iter_mut().enumerate(),
+            key: marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: self.entries.into_iter().enumerate(),
+            key: marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        if self.entries.is_empty() {
+            return Entry::Vacant(Vacant {
+                key,
+                slot: VacantSlot::Unallocated(&mut self.entries),
+                len: &mut self.len,
+            });
+        }
+
+        match OptionBucket::new(&mut self.entries[key.ordinal()]) {
+            OptionBucket::Some(bucket) => Entry::Occupied(Occupied {
+                key,
+                bucket,
+                len: &mut self.len,
+            }),
+            OptionBucket::None(bucket) => Entry::Vacant(Vacant {
+                key,
+                slot: VacantSlot::Allocated(bucket),
+                len: &mut self.len,
+            }),
+        }
+    }
+}
@@ -0,0 +1,467 @@
+use crate::map::{CapacityError, Entry, OccupiedEntry, VacantEntry};
+use crate::storage::Storage;
+use core::array;
+use core::hash::{self, Hasher};
+use core::mem;
+use core::slice;
+
+/// Storage for keys which must be hashed, like `u32` or `&'static str`, in a
+/// fixed number of slots which are stored inline instead of on the heap.
+///
+/// This makes it possible to use such keys without an allocator. The storage
+/// is picked for a variant of a derived key with `#[key(inline = N)]`.
+///
+/// Keys are placed with open addressing and linear probing. Once all `N`
+/// slots are in use, inserting a new key fails: [`Storage::try_insert`] and
+/// [`VacantEntry::try_insert`] hand back the key and value, while
+/// [`Storage::insert`] and [`VacantEntry::insert`] panic. Replacing the value
+/// of a key which is already present always succeeds.
+///
+/// # Examples
+///
+/// ```rust
+/// use fixed_map::storage::{InlineMapStorage, Storage};
+///
+/// let mut storage = InlineMapStorage::<u32, &str, 2>::default();
+/// assert_eq!(storage.insert(1, "a"), None);
+/// assert_eq!(storage.try_insert(2, "b"), Ok(None));
+/// assert!(storage.try_insert(3, "c").is_err());
+///
+/// assert_eq!(storage.get(&1), Some(&"a"));
+/// assert_eq!(storage.get(&3), None);
+/// assert_eq!(storage.len(), 2);
+/// ```
+pub struct InlineMapStorage<K, V, const N: usize> {
+    slots: [Option<(K, V)>; N],
+    len: usize,
+}
+
+/// The FNV-1a hash, which needs no random state and is therefore available
+/// without `std`.
+struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+impl<K, V, const N: usize> InlineMapStorage<K, V, N>
+where
+    K: Eq + hash::Hash,
+{
+    /// The slot where probing for the given key starts. Must only be called
+    /// when there are slots.
+    fn home(key: &K) -> usize {
+        let mut hasher = FnvHasher::default();
+        hash::Hash::hash(key, &mut hasher);
+        (hasher.finish() % N as u64) as usize
+    }
+
+    /// Find the slot of the given key, or else the vacant slot where it would
+    /// be inserted if there is one.
+    fn find(&self, key: &K) -> Result<usize, Option<usize>> {
+        if N == 0 {
+            return Err(None);
+        }
+
+        let home = Self::home(key);
+
+        for offset in 0..N {
+            let index = (home + offset) % N;
+
+            match &self.slots[index] {
+                Some((k, _)) if k == key => return Ok(index),
+                Some(..) => {}
+                None => return Err(Some(index)),
+            }
+        }
+
+        Err(None)
+    }
+
+    /// Occupy the vacant slot at the given index.
+    fn occupy(&mut self, index: usize, key: K, value: V) -> &mut V {
+        self.len += 1;
+        let (_, value) = self.slots[index].insert((key, value));
+        value
+    }
+
+    /// Take the entry out of the occupied slot at the given index.
+    fn vacate(&mut self, index: usize) -> V {
+        let (_, value) = self.slots[index]
+            .take()
+            .expect("occupied slot has an entry");
+        self.len -= 1;
+
+        // Lookups stop at the first vacant slot, so the entries which follow
+        // in the same run are shifted back into the hole unless that would
+        // move them before the slot where their probing starts.
+        let mut hole = index;
+        let mut next = (index + 1) % N;
+
+        while let Some((key, _)) = &self.slots[next] {
+            let home = Self::home(key);
+
+            let stays = if hole <= next {
+                hole < home && home <= next
+            } else {
+                hole < home || home <= next
+            };
+
+            if !stays {
+                self.slots[hole] = self.slots[next].take();
+                hole = next;
+            }
+
+            next = (next + 1) % N;
+        }
+
+        value
+    }
+}
+
+impl<K, V, const N: usize> Clone for InlineMapStorage<K, V, N>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        InlineMapStorage {
+            slots: self.slots.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<K, V, const N: usize> Default for InlineMapStorage<K, V, N> {
+    fn default() -> Self {
+        Self {
+            slots: array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V, const N: usize> PartialEq for InlineMapStorage<K, V, N>
+where
+    K: Eq + hash::Hash,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        // The slots depend on the order of insertion, so every entry is
+        // looked up in the other storage instead of comparing slots.
+        self.slots
+            .iter()
+            .flatten()
+            .all(|(key, value)| match other.find(key) {
+                Ok(index) => other.slots[index].as_ref().map(|(_, v)| v) == Some(value),
+                Err(..) => false,
+            })
+    }
+}
+
+impl<K, V, const N: usize> Eq for InlineMapStorage<K, V, N>
+where
+    K: Eq + hash::Hash,
+    V: Eq,
+{
+}
+
+pub struct Iter<'a, K, V> {
+    iter: slice::Iter<'a, Option<(K, V)>>,
+}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    fn clone(&self) -> Iter<'a, K, V> {
+        Iter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Clone,
+{
+    type Item = (K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = (&mut self.iter).flatten().next()?;
+        Some((key.clone(), value))
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    iter: slice::IterMut<'a, Option<(K, V)>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V>
+where
+    K: Clone,
+{
+    type Item = (K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = (&mut self.iter).flatten().next()?;
+        Some((key.clone(), value))
+    }
+}
+
+pub struct IntoIter<K, V, const N: usize> {
+    iter: array::IntoIter<Option<(K, V)>, N>,
+}
+
+impl<K, V, const N: usize> Iterator for IntoIter<K, V, N> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        (&mut self.iter).flatten().next()
+    }
+}
+
+pub struct Occupied<'a, K, V, const N: usize> {
+    storage: &'a mut InlineMapStorage<K, V, N>,
+    index: usize,
+}
+
+impl<'a, K, V, const N: usize> OccupiedEntry<'a, K, V> for Occupied<'a, K, V, N>
+where
+    K: Clone + Eq + hash::Hash,
+{
+    #[inline]
+    fn key(&self) -> K {
+        match &self.storage.slots[self.index] {
+            Some((key, _)) => key.clone(),
+            None => unreachable!("occupied slot has an entry"),
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        match &self.storage.slots[self.index] {
+            Some((_, value)) => value,
+            None => unreachable!("occupied slot has an entry"),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        match &mut self.storage.slots[self.index] {
+            Some((_, value)) => value,
+            None => unreachable!("occupied slot has an entry"),
+        }
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        match &mut self.storage.slots[self.index] {
+            Some((_, value)) => value,
+            None => unreachable!("occupied slot has an entry"),
+        }
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.storage.vacate(self.index)
+    }
+}
+
+pub struct Vacant<'a, K, V, const N: usize> {
+    storage: &'a mut InlineMapStorage<K, V, N>,
+    key: K,
+    index: Option<usize>,
+}
+
+impl<'a, K, V, const N: usize> VacantEntry<'a, K, V> for Vacant<'a, K, V, N>
+where
+    K: Clone + Eq + hash::Hash,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key.clone()
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        match self.index {
+            Some(index) => self.storage.occupy(index, self.key, value),
+            None => panic!("no capacity left in inline storage of {} slots", N),
+        }
+    }
+
+    #[inline]
+    fn try_insert(self, value: V) -> Result<&'a mut V, CapacityError<K, V>> {
+        match self.index {
+            Some(index) => Ok(self.storage.occupy(index, self.key, value)),
+            None => Err(CapacityError::new(self.key, value)),
+        }
+    }
+}
+
+impl<K, V, const N: usize> Storage<K, V> for InlineMapStorage<K, V, N>
+where
+    K: Clone + Eq + hash::Hash,
+{
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<K, V, N>;
+    type Occupied<'this>
+        = Occupied<'this, K, V, N>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V, N>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.try_insert(key, value) {
+            Ok(existing) => existing,
+            Err(..) => panic!("no capacity left in inline storage of {} slots", N),
+        }
+    }
+
+    #[inline]
+    fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<K, V>> {
+        match self.find(&key) {
+            Ok(index) => match &mut self.slots[index] {
+                Some((_, existing)) => Ok(Some(mem::replace(existing, value))),
+                None => unreachable!("occupied slot has an entry"),
+            },
+            Err(Some(index)) => {
+                self.occupy(index, key, value);
+                Ok(None)
+            }
+            Err(None) => Err(CapacityError::new(key, value)),
+        }
+    }
+
+    #[inline]
+    fn get(&self, key: &K) -> Option<&V> {
+        let index = self.find(key).ok()?;
+        self.slots[index].as_ref().map(|(_, value)| value)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key).ok()?;
+        self.slots[index].as_mut().map(|(_, value)| value)
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.find(key).ok()?;
+        Some(self.vacate(index))
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.slots = array::from_fn(|_| None);
+        self.len = 0;
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        // Removing entries in place shifts others around while iterating, so
+        // the entries which are kept are inserted again instead.
+        let slots = mem::replace(&mut self.slots, array::from_fn(|_| None));
+        self.len = 0;
+
+        for (key, mut value) in IntoIterator::into_iter(slots).flatten() {
+            if func(key.clone(), &mut value) {
+                match self.find(&key) {
+                    Err(Some(index)) => {
+                        self.occupy(index, key, value);
+                    }
+                    _ => unreachable!("kept entries fit in the storage they came from"),
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            iter: self.slots.iter(),
+        }
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            iter: self.slots.iter_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: IntoIterator::into_iter(self.slots),
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        match self.find(&key) {
+            Ok(index) => Entry::Occupied(Occupied {
+                storage: self,
+                index,
+            }),
+            Err(index) => Entry::Vacant(Vacant {
+                storage: self,
+                key,
+                index,
+            }),
+        }
+    }
+}
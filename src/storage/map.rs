@@ -1,5 +1,6 @@
+use crate::map::{Entry, OccupiedEntry, VacantEntry};
 use crate::storage::Storage;
-use std::hash;
+use core::hash;
 
 /// Storage for static types that must be stored in a map.
 pub struct MapStorage<K, V> {
@@ -46,47 +47,147 @@ where
 {
 }
 
-pub struct Iter<K, V> {
-    iter: std::vec::IntoIter<(K, *const V)>,
+pub struct Iter<'a, K, V> {
+    iter: hashbrown::hash_map::Iter<'a, K, V>,
 }
 
-impl<K, V> Clone for Iter<K, V>
-where
-    K: Copy,
-{
-    fn clone(&self) -> Iter<K, V> {
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    fn clone(&self) -> Iter<'a, K, V> {
         Iter {
             iter: self.iter.clone(),
         }
     }
 }
 
-impl<K, V> Iterator for Iter<K, V> {
-    type Item = (K, *const V);
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Clone,
+{
+    type Item = (K, &'a V);
 
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        self.iter.next().map(|(k, v)| (k.clone(), v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
 
-pub struct IterMut<K, V> {
-    iter: std::vec::IntoIter<(K, *mut V)>,
+pub struct IterMut<'a, K, V> {
+    iter: hashbrown::hash_map::IterMut<'a, K, V>,
 }
 
-impl<K, V> Iterator for IterMut<K, V> {
-    type Item = (K, *mut V);
+impl<'a, K, V> Iterator for IterMut<'a, K, V>
+where
+    K: Clone,
+{
+    type Item = (K, &'a mut V);
 
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        self.iter.next().map(|(k, v)| (k.clone(), v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct Occupied<'a, K, V> {
+    inner: hashbrown::hash_map::OccupiedEntry<'a, K, V, hashbrown::hash_map::DefaultHashBuilder>,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
+where
+    K: Clone,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.inner.key().clone()
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.insert(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.inner.remove()
+    }
+}
+
+pub struct Vacant<'a, K, V> {
+    inner: hashbrown::hash_map::VacantEntry<'a, K, V, hashbrown::hash_map::DefaultHashBuilder>,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
+where
+    K: Clone + Eq + hash::Hash,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.inner.key().clone()
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.inner.insert(value)
     }
 }
 
 impl<K, V> Storage<K, V> for MapStorage<K, V>
 where
-    K: Copy + Eq + hash::Hash,
+    K: Clone + Eq + hash::Hash,
 {
-    type Iter = Iter<K, V>;
-    type IterMut = IterMut<K, V>;
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = hashbrown::hash_map::IntoIter<K, V>;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
 
     #[inline]
     fn insert(&mut self, key: K, value: V) -> Option<V> {
@@ -94,18 +195,18 @@ where
     }
 
     #[inline]
-    fn get(&self, key: K) -> Option<&V> {
-        self.inner.get(&key)
+    fn get(&self, key: &K) -> Option<&V> {
+        self.inner.get(key)
     }
 
     #[inline]
-    fn get_mut(&mut self, key: K) -> Option<&mut V> {
-        self.inner.get_mut(&key)
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.inner.get_mut(key)
     }
 
     #[inline]
-    fn remove(&mut self, key: K) -> Option<V> {
-        self.inner.remove(&key)
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.inner.remove(key)
     }
 
     #[inline]
@@ -114,26 +215,37 @@ where
     }
 
     #[inline]
-    fn iter(&self) -> Self::Iter {
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        self.inner.retain(|k, v| func(k.clone(), v));
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
         Iter {
-            iter: self
-                .inner
-                .iter()
-                .map(|(k, v)| (*k, v as *const V))
-                .collect::<Vec<_>>()
-                .into_iter(),
+            iter: self.inner.iter(),
         }
     }
 
     #[inline]
-    fn iter_mut(&mut self) -> Self::IterMut {
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
         IterMut {
-            iter: self
-                .inner
-                .iter_mut()
-                .map(|(k, v)| (*k, v as *mut V))
-                .collect::<Vec<_>>()
-                .into_iter(),
+            iter: self.inner.iter_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        match self.inner.entry(key) {
+            hashbrown::hash_map::Entry::Occupied(inner) => Entry::Occupied(Occupied { inner }),
+            hashbrown::hash_map::Entry::Vacant(inner) => Entry::Vacant(Vacant { inner }),
         }
     }
 }
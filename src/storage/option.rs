@@ -1,5 +1,6 @@
+use crate::map::{CapacityError, Entry, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
 use crate::{key::Key, storage::Storage};
-use std::mem;
 
 /// Storage for `Option<T>`s.
 pub struct OptionStorage<K, V>
@@ -55,31 +56,60 @@ where
 {
 }
 
-pub struct Iter<K, V>
+pub struct Iter<'a, K, V>
 where
-    K: Key<K, V>,
+    K: 'a + Key<K, V>,
+    V: 'a,
 {
-    some: <K::Storage as Storage<K, V>>::Iter,
-    none: Option<*const V>,
+    some: <K::Storage as Storage<K, V>>::Iter<'a>,
+    none: Option<&'a V>,
 }
 
-impl<K, V> Clone for Iter<K, V>
+impl<'a, K, V> Clone for Iter<'a, K, V>
 where
     K: Key<K, V>,
 {
-    fn clone(&self) -> Iter<K, V> {
+    fn clone(&self) -> Iter<'a, K, V> {
         Iter {
             some: self.some.clone(),
-            none: self.none.clone(),
+            none: self.none,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Key<K, V>,
+{
+    type Item = (Option<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((k, v)) = self.some.next() {
+            return Some((Some(k), v));
         }
+
+        if let Some(v) = self.none.take() {
+            return Some((None, v));
+        }
+
+        None
     }
 }
 
-impl<K, V> Iterator for Iter<K, V>
+pub struct IterMut<'a, K, V>
+where
+    K: 'a + Key<K, V>,
+    V: 'a,
+{
+    some: <K::Storage as Storage<K, V>>::IterMut<'a>,
+    none: Option<&'a mut V>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V>
 where
     K: Key<K, V>,
 {
-    type Item = (Option<K>, *const V);
+    type Item = (Option<K>, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some((k, v)) = self.some.next() {
@@ -94,19 +124,19 @@ where
     }
 }
 
-pub struct IterMut<K, V>
+pub struct IntoIter<K, V>
 where
     K: Key<K, V>,
 {
-    some: <K::Storage as Storage<K, V>>::IterMut,
-    none: Option<*mut V>,
+    some: <K::Storage as Storage<K, V>>::IntoIter,
+    none: Option<V>,
 }
 
-impl<K, V> Iterator for IterMut<K, V>
+impl<K, V> Iterator for IntoIter<K, V>
 where
     K: Key<K, V>,
 {
-    type Item = (Option<K>, *mut V);
+    type Item = (Option<K>, V);
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some((k, v)) = self.some.next() {
@@ -121,23 +151,168 @@ where
     }
 }
 
+pub enum Occupied<'a, K, V>
+where
+    K: 'a + Key<K, V>,
+    V: 'a,
+{
+    Some(<K::Storage as Storage<K, V>>::Occupied<'a>),
+    None(SomeBucket<'a, V>),
+}
+
+impl<'a, K, V> OccupiedEntry<'a, Option<K>, V> for Occupied<'a, K, V>
+where
+    K: Key<K, V>,
+{
+    #[inline]
+    fn key(&self) -> Option<K> {
+        match self {
+            Occupied::Some(entry) => Some(entry.key()),
+            Occupied::None(_) => None,
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        match self {
+            Occupied::Some(entry) => entry.get(),
+            Occupied::None(entry) => entry.get(),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        match self {
+            Occupied::Some(entry) => entry.get_mut(),
+            Occupied::None(entry) => entry.get_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        match self {
+            Occupied::Some(entry) => entry.into_mut(),
+            Occupied::None(entry) => entry.into_mut(),
+        }
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        match self {
+            Occupied::Some(entry) => entry.insert(value),
+            Occupied::None(entry) => entry.replace(value),
+        }
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        match self {
+            Occupied::Some(entry) => entry.remove(),
+            Occupied::None(entry) => entry.take(),
+        }
+    }
+}
+
+pub enum Vacant<'a, K, V>
+where
+    K: 'a + Key<K, V>,
+    V: 'a,
+{
+    Some(<K::Storage as Storage<K, V>>::Vacant<'a>),
+    None(NoneBucket<'a, V>),
+}
+
+impl<'a, K, V> VacantEntry<'a, Option<K>, V> for Vacant<'a, K, V>
+where
+    K: Key<K, V>,
+{
+    #[inline]
+    fn key(&self) -> Option<K> {
+        match self {
+            Vacant::Some(entry) => Some(entry.key()),
+            Vacant::None(_) => None,
+        }
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        match self {
+            Vacant::Some(entry) => entry.insert(value),
+            Vacant::None(entry) => entry.insert(value),
+        }
+    }
+
+    #[inline]
+    fn try_insert(self, value: V) -> Result<&'a mut V, CapacityError<Option<K>, V>> {
+        match self {
+            Vacant::Some(entry) => entry.try_insert(value).map_err(|error| {
+                let (key, value) = error.into_inner();
+                CapacityError::new(Some(key), value)
+            }),
+            Vacant::None(entry) => Ok(entry.insert(value)),
+        }
+    }
+}
+
 impl<K, V> Storage<Option<K>, V> for OptionStorage<K, V>
 where
     K: Key<K, V>,
 {
-    type Iter = Iter<K, V>;
-    type IterMut = IterMut<K, V>;
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<K, V>;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.some.len() + usize::from(self.none.is_some())
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.some.is_empty() && self.none.is_none()
+    }
 
     #[inline]
     fn insert(&mut self, key: Option<K>, value: V) -> Option<V> {
         match key {
             Some(key) => self.some.insert(key, value),
-            None => mem::replace(&mut self.none, Some(value)),
+            None => self.none.replace(value),
         }
     }
 
     #[inline]
-    fn get(&self, key: Option<K>) -> Option<&V> {
+    fn try_insert(
+        &mut self,
+        key: Option<K>,
+        value: V,
+    ) -> Result<Option<V>, CapacityError<Option<K>, V>> {
+        match key {
+            Some(key) => self.some.try_insert(key, value).map_err(|error| {
+                let (key, value) = error.into_inner();
+                CapacityError::new(Some(key), value)
+            }),
+            None => Ok(self.none.replace(value)),
+        }
+    }
+
+    #[inline]
+    fn get(&self, key: &Option<K>) -> Option<&V> {
         match key {
             Some(key) => self.some.get(key),
             None => self.none.as_ref(),
@@ -145,7 +320,7 @@ where
     }
 
     #[inline]
-    fn get_mut(&mut self, key: Option<K>) -> Option<&mut V> {
+    fn get_mut(&mut self, key: &Option<K>) -> Option<&mut V> {
         match key {
             Some(key) => self.some.get_mut(key),
             None => self.none.as_mut(),
@@ -153,10 +328,10 @@ where
     }
 
     #[inline]
-    fn remove(&mut self, key: Option<K>) -> Option<V> {
+    fn remove(&mut self, key: &Option<K>) -> Option<V> {
         match key {
             Some(key) => self.some.remove(key),
-            None => mem::replace(&mut self.none, None),
+            None => self.none.take(),
         }
     }
 
@@ -167,18 +342,54 @@ where
     }
 
     #[inline]
-    fn iter(&self) -> Self::Iter {
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Option<K>, &mut V) -> bool,
+    {
+        self.some.retain(|k, v| func(Some(k), v));
+
+        if let Some(none) = self.none.as_mut() {
+            if !func(None, none) {
+                self.none = None;
+            }
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
         Iter {
             some: self.some.iter(),
-            none: self.none.as_ref().map(|v| v as *const V),
+            none: self.none.as_ref(),
         }
     }
 
     #[inline]
-    fn iter_mut(&mut self) -> Self::IterMut {
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
         IterMut {
             some: self.some.iter_mut(),
-            none: self.none.as_mut().map(|v| v as *mut V),
+            none: self.none.as_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            some: self.some.into_iter(),
+            none: self.none,
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, key: Option<K>) -> Entry<'_, Self, Option<K>, V> {
+        match key {
+            Some(key) => match self.some.entry(key) {
+                Entry::Occupied(entry) => Entry::Occupied(Occupied::Some(entry)),
+                Entry::Vacant(entry) => Entry::Vacant(Vacant::Some(entry)),
+            },
+            None => match OptionBucket::new(&mut self.none) {
+                OptionBucket::Some(entry) => Entry::Occupied(Occupied::None(entry)),
+                OptionBucket::None(entry) => Entry::Vacant(Vacant::None(entry)),
+            },
         }
     }
 }
@@ -0,0 +1,315 @@
+use crate::map::{Entry, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
+use crate::storage::Storage;
+use core::cmp::Ordering;
+
+/// Storage for `Ordering`s.
+pub struct OrderingStorage<V> {
+    less: Option<V>,
+    equal: Option<V>,
+    greater: Option<V>,
+}
+
+impl<V> Clone for OrderingStorage<V>
+where
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        OrderingStorage {
+            less: self.less.clone(),
+            equal: self.equal.clone(),
+            greater: self.greater.clone(),
+        }
+    }
+}
+
+impl<V> Default for OrderingStorage<V> {
+    fn default() -> Self {
+        Self {
+            less: Default::default(),
+            equal: Default::default(),
+            greater: Default::default(),
+        }
+    }
+}
+
+impl<V> PartialEq for OrderingStorage<V>
+where
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.less == other.less && self.equal == other.equal && self.greater == other.greater
+    }
+}
+
+impl<V> Eq for OrderingStorage<V> where V: Eq {}
+
+pub struct Iter<'a, V> {
+    less: Option<&'a V>,
+    equal: Option<&'a V>,
+    greater: Option<&'a V>,
+}
+
+impl<'a, V> Clone for Iter<'a, V> {
+    fn clone(&self) -> Iter<'a, V> {
+        Iter {
+            less: self.less,
+            equal: self.equal,
+            greater: self.greater,
+        }
+    }
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (Ordering, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(less) = self.less.take() {
+            return Some((Ordering::Less, less));
+        }
+
+        if let Some(equal) = self.equal.take() {
+            return Some((Ordering::Equal, equal));
+        }
+
+        if let Some(greater) = self.greater.take() {
+            return Some((Ordering::Greater, greater));
+        }
+
+        None
+    }
+}
+
+pub struct IterMut<'a, V> {
+    less: Option<&'a mut V>,
+    equal: Option<&'a mut V>,
+    greater: Option<&'a mut V>,
+}
+
+impl<'a, V> Iterator for IterMut<'a, V> {
+    type Item = (Ordering, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(less) = self.less.take() {
+            return Some((Ordering::Less, less));
+        }
+
+        if let Some(equal) = self.equal.take() {
+            return Some((Ordering::Equal, equal));
+        }
+
+        if let Some(greater) = self.greater.take() {
+            return Some((Ordering::Greater, greater));
+        }
+
+        None
+    }
+}
+
+pub struct IntoIter<V> {
+    less: Option<V>,
+    equal: Option<V>,
+    greater: Option<V>,
+}
+
+impl<V> Iterator for IntoIter<V> {
+    type Item = (Ordering, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(less) = self.less.take() {
+            return Some((Ordering::Less, less));
+        }
+
+        if let Some(equal) = self.equal.take() {
+            return Some((Ordering::Equal, equal));
+        }
+
+        if let Some(greater) = self.greater.take() {
+            return Some((Ordering::Greater, greater));
+        }
+
+        None
+    }
+}
+
+pub struct Occupied<'a, V> {
+    key: Ordering,
+    inner: SomeBucket<'a, V>,
+}
+
+impl<'a, V> OccupiedEntry<'a, Ordering, V> for Occupied<'a, V> {
+    #[inline]
+    fn key(&self) -> Ordering {
+        self.key
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.replace(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.inner.take()
+    }
+}
+
+pub struct Vacant<'a, V> {
+    key: Ordering,
+    inner: NoneBucket<'a, V>,
+}
+
+impl<'a, V> VacantEntry<'a, Ordering, V> for Vacant<'a, V> {
+    #[inline]
+    fn key(&self) -> Ordering {
+        self.key
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.inner.insert(value)
+    }
+}
+
+impl<V> OrderingStorage<V> {
+    /// Access the slot of the given key.
+    #[inline]
+    fn slot_mut(&mut self, key: Ordering) -> &mut Option<V> {
+        match key {
+            Ordering::Less => &mut self.less,
+            Ordering::Equal => &mut self.equal,
+            Ordering::Greater => &mut self.greater,
+        }
+    }
+}
+
+impl<V> Storage<Ordering, V> for OrderingStorage<V> {
+    type Iter<'this>
+        = Iter<'this, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<V>;
+    type Occupied<'this>
+        = Occupied<'this, V>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        usize::from(self.less.is_some())
+            + usize::from(self.equal.is_some())
+            + usize::from(self.greater.is_some())
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.less.is_none() && self.equal.is_none() && self.greater.is_none()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Ordering, value: V) -> Option<V> {
+        self.slot_mut(key).replace(value)
+    }
+
+    #[inline]
+    fn get(&self, key: &Ordering) -> Option<&V> {
+        match *key {
+            Ordering::Less => self.less.as_ref(),
+            Ordering::Equal => self.equal.as_ref(),
+            Ordering::Greater => self.greater.as_ref(),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: &Ordering) -> Option<&mut V> {
+        self.slot_mut(*key).as_mut()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &Ordering) -> Option<V> {
+        self.slot_mut(*key).take()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.less = None;
+        self.equal = None;
+        self.greater = None;
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Ordering, &mut V) -> bool,
+    {
+        for key in [Ordering::Less, Ordering::Equal, Ordering::Greater] {
+            let slot = self.slot_mut(key);
+
+            if let Some(value) = slot.as_mut() {
+                if !func(key, value) {
+                    *slot = None;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            less: self.less.as_ref(),
+            equal: self.equal.as_ref(),
+            greater: self.greater.as_ref(),
+        }
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            less: self.less.as_mut(),
+            equal: self.equal.as_mut(),
+            greater: self.greater.as_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            less: self.less,
+            equal: self.equal,
+            greater: self.greater,
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, key: Ordering) -> Entry<'_, Self, Ordering, V> {
+        match OptionBucket::new(self.slot_mut(key)) {
+            OptionBucket::Some(inner) => Entry::Occupied(Occupied { key, inner }),
+            OptionBucket::None(inner) => Entry::Vacant(Vacant { key, inner }),
+        }
+    }
+}
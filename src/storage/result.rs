@@ -0,0 +1,412 @@
+use crate::map::{CapacityError, Entry, OccupiedEntry, VacantEntry};
+use crate::{key::Key, storage::Storage};
+
+/// Storage for `Result<T, E>`s.
+pub struct ResultStorage<T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    ok: T::Storage,
+    err: E::Storage,
+}
+
+impl<T, E, V> Clone for ResultStorage<T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+    T::Storage: Clone,
+    E::Storage: Clone,
+{
+    fn clone(&self) -> Self {
+        ResultStorage {
+            ok: self.ok.clone(),
+            err: self.err.clone(),
+        }
+    }
+}
+
+impl<T, E, V> Default for ResultStorage<T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    fn default() -> Self {
+        Self {
+            ok: Default::default(),
+            err: Default::default(),
+        }
+    }
+}
+
+impl<T, E, V> PartialEq for ResultStorage<T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+    T::Storage: PartialEq,
+    E::Storage: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ok == other.ok && self.err == other.err
+    }
+}
+
+impl<T, E, V> Eq for ResultStorage<T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+    T::Storage: Eq,
+    E::Storage: Eq,
+{
+}
+
+pub struct Iter<'a, T, E, V>
+where
+    T: 'a + Key<T, V>,
+    E: 'a + Key<E, V>,
+    V: 'a,
+{
+    ok: <T::Storage as Storage<T, V>>::Iter<'a>,
+    err: <E::Storage as Storage<E, V>>::Iter<'a>,
+}
+
+impl<'a, T, E, V> Clone for Iter<'a, T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    fn clone(&self) -> Iter<'a, T, E, V> {
+        Iter {
+            ok: self.ok.clone(),
+            err: self.err.clone(),
+        }
+    }
+}
+
+impl<'a, T, E, V> Iterator for Iter<'a, T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    type Item = (Result<T, E>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((k, v)) = self.ok.next() {
+            return Some((Ok(k), v));
+        }
+
+        if let Some((k, v)) = self.err.next() {
+            return Some((Err(k), v));
+        }
+
+        None
+    }
+}
+
+pub struct IterMut<'a, T, E, V>
+where
+    T: 'a + Key<T, V>,
+    E: 'a + Key<E, V>,
+    V: 'a,
+{
+    ok: <T::Storage as Storage<T, V>>::IterMut<'a>,
+    err: <E::Storage as Storage<E, V>>::IterMut<'a>,
+}
+
+impl<'a, T, E, V> Iterator for IterMut<'a, T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    type Item = (Result<T, E>, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((k, v)) = self.ok.next() {
+            return Some((Ok(k), v));
+        }
+
+        if let Some((k, v)) = self.err.next() {
+            return Some((Err(k), v));
+        }
+
+        None
+    }
+}
+
+pub struct IntoIter<T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    ok: <T::Storage as Storage<T, V>>::IntoIter,
+    err: <E::Storage as Storage<E, V>>::IntoIter,
+}
+
+impl<T, E, V> Iterator for IntoIter<T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    type Item = (Result<T, E>, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((k, v)) = self.ok.next() {
+            return Some((Ok(k), v));
+        }
+
+        if let Some((k, v)) = self.err.next() {
+            return Some((Err(k), v));
+        }
+
+        None
+    }
+}
+
+pub enum Occupied<'a, T, E, V>
+where
+    T: 'a + Key<T, V>,
+    E: 'a + Key<E, V>,
+    V: 'a,
+{
+    Ok(<T::Storage as Storage<T, V>>::Occupied<'a>),
+    Err(<E::Storage as Storage<E, V>>::Occupied<'a>),
+}
+
+impl<'a, T, E, V> OccupiedEntry<'a, Result<T, E>, V> for Occupied<'a, T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    #[inline]
+    fn key(&self) -> Result<T, E> {
+        match self {
+            Occupied::Ok(entry) => Ok(entry.key()),
+            Occupied::Err(entry) => Err(entry.key()),
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        match self {
+            Occupied::Ok(entry) => entry.get(),
+            Occupied::Err(entry) => entry.get(),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        match self {
+            Occupied::Ok(entry) => entry.get_mut(),
+            Occupied::Err(entry) => entry.get_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        match self {
+            Occupied::Ok(entry) => entry.into_mut(),
+            Occupied::Err(entry) => entry.into_mut(),
+        }
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        match self {
+            Occupied::Ok(entry) => entry.insert(value),
+            Occupied::Err(entry) => entry.insert(value),
+        }
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        match self {
+            Occupied::Ok(entry) => entry.remove(),
+            Occupied::Err(entry) => entry.remove(),
+        }
+    }
+}
+
+pub enum Vacant<'a, T, E, V>
+where
+    T: 'a + Key<T, V>,
+    E: 'a + Key<E, V>,
+    V: 'a,
+{
+    Ok(<T::Storage as Storage<T, V>>::Vacant<'a>),
+    Err(<E::Storage as Storage<E, V>>::Vacant<'a>),
+}
+
+impl<'a, T, E, V> VacantEntry<'a, Result<T, E>, V> for Vacant<'a, T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    #[inline]
+    fn key(&self) -> Result<T, E> {
+        match self {
+            Vacant::Ok(entry) => Ok(entry.key()),
+            Vacant::Err(entry) => Err(entry.key()),
+        }
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        match self {
+            Vacant::Ok(entry) => entry.insert(value),
+            Vacant::Err(entry) => entry.insert(value),
+        }
+    }
+
+    #[inline]
+    fn try_insert(self, value: V) -> Result<&'a mut V, CapacityError<Result<T, E>, V>> {
+        match self {
+            Vacant::Ok(entry) => entry.try_insert(value).map_err(|error| {
+                let (key, value) = error.into_inner();
+                CapacityError::new(Ok(key), value)
+            }),
+            Vacant::Err(entry) => entry.try_insert(value).map_err(|error| {
+                let (key, value) = error.into_inner();
+                CapacityError::new(Err(key), value)
+            }),
+        }
+    }
+}
+
+impl<T, E, V> Storage<Result<T, E>, V> for ResultStorage<T, E, V>
+where
+    T: Key<T, V>,
+    E: Key<E, V>,
+{
+    type Iter<'this>
+        = Iter<'this, T, E, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, T, E, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<T, E, V>;
+    type Occupied<'this>
+        = Occupied<'this, T, E, V>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, T, E, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.ok.len() + self.err.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.ok.is_empty() && self.err.is_empty()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Result<T, E>, value: V) -> Option<V> {
+        match key {
+            Ok(key) => self.ok.insert(key, value),
+            Err(key) => self.err.insert(key, value),
+        }
+    }
+
+    #[inline]
+    fn try_insert(
+        &mut self,
+        key: Result<T, E>,
+        value: V,
+    ) -> Result<Option<V>, CapacityError<Result<T, E>, V>> {
+        match key {
+            Ok(key) => self.ok.try_insert(key, value).map_err(|error| {
+                let (key, value) = error.into_inner();
+                CapacityError::new(Ok(key), value)
+            }),
+            Err(key) => self.err.try_insert(key, value).map_err(|error| {
+                let (key, value) = error.into_inner();
+                CapacityError::new(Err(key), value)
+            }),
+        }
+    }
+
+    #[inline]
+    fn get(&self, key: &Result<T, E>) -> Option<&V> {
+        match key {
+            Ok(key) => self.ok.get(key),
+            Err(key) => self.err.get(key),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: &Result<T, E>) -> Option<&mut V> {
+        match key {
+            Ok(key) => self.ok.get_mut(key),
+            Err(key) => self.err.get_mut(key),
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &Result<T, E>) -> Option<V> {
+        match key {
+            Ok(key) => self.ok.remove(key),
+            Err(key) => self.err.remove(key),
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.ok.clear();
+        self.err.clear();
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Result<T, E>, &mut V) -> bool,
+    {
+        self.ok.retain(|k, v| func(Ok(k), v));
+        self.err.retain(|k, v| func(Err(k), v));
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            ok: self.ok.iter(),
+            err: self.err.iter(),
+        }
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            ok: self.ok.iter_mut(),
+            err: self.err.iter_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            ok: self.ok.into_iter(),
+            err: self.err.into_iter(),
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, key: Result<T, E>) -> Entry<'_, Self, Result<T, E>, V> {
+        match key {
+            Ok(key) => match self.ok.entry(key) {
+                Entry::Occupied(entry) => Entry::Occupied(Occupied::Ok(entry)),
+                Entry::Vacant(entry) => Entry::Vacant(Vacant::Ok(entry)),
+            },
+            Err(key) => match self.err.entry(key) {
+                Entry::Occupied(entry) => Entry::Occupied(Occupied::Err(entry)),
+                Entry::Vacant(entry) => Entry::Vacant(Vacant::Err(entry)),
+            },
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::map::CapacityError;
+use crate::storage::Storage;
+use core::marker;
+
+/// The trait defining how storage works for a [`Set`](struct.Set.html).
+///
+/// This is implemented for every [`Storage`] which stores `()` as its value,
+/// so that keys which have no dedicated set storage can be stored in a set
+/// through their map storage.
+///
+/// # Type Arguments
+///
+/// - `K` is the key being stored.
+pub trait SetStorage<K>: Default {
+    /// Immutable iterator over storage.
+    type Iter<'this>: Clone + Iterator<Item = K>
+    where
+        Self: 'this;
+
+    /// Owning iterator over storage.
+    type IntoIter: Iterator<Item = K>;
+
+    /// This is the storage abstraction for [`Set::len`](struct.Set.html#method.len).
+    fn len(&self) -> usize;
+
+    /// This is the storage abstraction for [`Set::is_empty`](struct.Set.html#method.is_empty).
+    fn is_empty(&self) -> bool;
+
+    /// This is the storage abstraction for [`Set::insert`](struct.Set.html#method.insert).
+    fn insert(&mut self, key: K) -> bool;
+
+    /// This is the storage abstraction for [`Set::try_insert`](struct.Set.html#method.try_insert).
+    ///
+    /// The default implementation defers to [`SetStorage::insert`], for
+    /// storage which always has room.
+    #[inline]
+    fn try_insert(&mut self, key: K) -> Result<bool, CapacityError<K, ()>> {
+        Ok(self.insert(key))
+    }
+
+    /// This is the storage abstraction for [`Set::contains`](struct.Set.html#method.contains).
+    fn contains(&self, key: &K) -> bool;
+
+    /// This is the storage abstraction for [`Set::remove`](struct.Set.html#method.remove).
+    fn remove(&mut self, key: &K) -> bool;
+
+    /// This is the storage abstraction for [`Set::retain`](struct.Set.html#method.retain).
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(K) -> bool;
+
+    /// This is the storage abstraction for [`Set::clear`](struct.Set.html#method.clear).
+    fn clear(&mut self);
+
+    /// This is the storage abstraction for [`Set::iter`](struct.Set.html#method.iter).
+    fn iter(&self) -> Self::Iter<'_>;
+
+    /// This is the storage abstraction for [`Set::into_iter`](struct.Set.html#method.into_iter).
+    fn into_iter(self) -> Self::IntoIter;
+}
+
+pub struct Iter<'a, K, S>
+where
+    S: 'a + Storage<K, ()>,
+{
+    iter: S::Iter<'a>,
+    key: marker::PhantomData<K>,
+}
+
+impl<'a, K, S> Clone for Iter<'a, K, S>
+where
+    S: Storage<K, ()>,
+{
+    fn clone(&self) -> Self {
+        Iter {
+            iter: self.iter.clone(),
+            key: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, K, S> Iterator for Iter<'a, K, S>
+where
+    S: Storage<K, ()>,
+{
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, _)| k)
+    }
+}
+
+pub struct IntoIter<K, S>
+where
+    S: Storage<K, ()>,
+{
+    iter: S::IntoIter,
+    key: marker::PhantomData<K>,
+}
+
+impl<K, S> Iterator for IntoIter<K, S>
+where
+    S: Storage<K, ()>,
+{
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, _)| k)
+    }
+}
+
+impl<K, S> SetStorage<K> for S
+where
+    S: Storage<K, ()>,
+{
+    type Iter<'this>
+        = Iter<'this, K, S>
+    where
+        Self: 'this;
+    type IntoIter = IntoIter<K, S>;
+
+    #[inline]
+    fn len(&self) -> usize {
+        Storage::len(self)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        Storage::is_empty(self)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K) -> bool {
+        Storage::insert(self, key, ()).is_none()
+    }
+
+    #[inline]
+    fn try_insert(&mut self, key: K) -> Result<bool, CapacityError<K, ()>> {
+        Ok(Storage::try_insert(self, key, ())?.is_none())
+    }
+
+    #[inline]
+    fn contains(&self, key: &K) -> bool {
+        Storage::get(self, key).is_some()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &K) -> bool {
+        Storage::remove(self, key).is_some()
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(K) -> bool,
+    {
+        Storage::retain(self, |k, _| f(k));
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        Storage::clear(self);
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            iter: Storage::iter(self),
+            key: marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: Storage::into_iter(self),
+            key: marker::PhantomData,
+        }
+    }
+}
@@ -1,6 +1,7 @@
+use crate::map::{Entry, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
 use crate::storage::Storage;
-use std::marker;
-use std::mem;
+use core::marker;
 
 /// Storage types that can only inhabit a single value (like `()`).
 pub struct SingletonStorage<K, V> {
@@ -40,13 +41,13 @@ where
 
 impl<K, V> Eq for SingletonStorage<K, V> where V: Eq {}
 
-pub struct Iter<K, V> {
-    value: Option<(K, *const V)>,
+pub struct Iter<'a, K, V> {
+    value: Option<(K, &'a V)>,
 }
 
-impl<K, V> Clone for Iter<K, V>
+impl<'a, K, V> Clone for Iter<'a, K, V>
 where
-    K: Copy,
+    K: Clone,
 {
     fn clone(&self) -> Self {
         Iter {
@@ -55,51 +56,150 @@ where
     }
 }
 
-impl<K, V> Iterator for Iter<K, V> {
-    type Item = (K, *const V);
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.value.take()
     }
 }
 
-pub struct IterMut<K, V> {
-    value: Option<(K, *mut V)>,
+pub struct IterMut<'a, K, V> {
+    value: Option<(K, &'a mut V)>,
 }
 
-impl<K, V> Iterator for IterMut<K, V> {
-    type Item = (K, *mut V);
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.value.take()
     }
 }
 
+pub struct IntoIter<K, V> {
+    value: Option<(K, V)>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.value.take()
+    }
+}
+
+pub struct Occupied<'a, K, V> {
+    inner: SomeBucket<'a, V>,
+    key: marker::PhantomData<K>,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
+where
+    K: Default,
+{
+    #[inline]
+    fn key(&self) -> K {
+        K::default()
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.replace(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.inner.take()
+    }
+}
+
+pub struct Vacant<'a, K, V> {
+    inner: NoneBucket<'a, V>,
+    key: marker::PhantomData<K>,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
+where
+    K: Default,
+{
+    #[inline]
+    fn key(&self) -> K {
+        K::default()
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.inner.insert(value)
+    }
+}
+
 impl<K, V> Storage<K, V> for SingletonStorage<K, V>
 where
-    K: Copy + Default,
+    K: Clone + Default,
 {
-    type Iter = Iter<K, V>;
-    type IterMut = IterMut<K, V>;
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<K, V>;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        usize::from(self.inner.is_some())
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.inner.is_none()
+    }
 
     #[inline]
     fn insert(&mut self, _: K, value: V) -> Option<V> {
-        mem::replace(&mut self.inner, Some(value))
+        self.inner.replace(value)
     }
 
     #[inline]
-    fn get(&self, _: K) -> Option<&V> {
+    fn get(&self, _: &K) -> Option<&V> {
         self.inner.as_ref()
     }
 
     #[inline]
-    fn get_mut(&mut self, _: K) -> Option<&mut V> {
+    fn get_mut(&mut self, _: &K) -> Option<&mut V> {
         self.inner.as_mut()
     }
 
     #[inline]
-    fn remove(&mut self, _: K) -> Option<V> {
-        mem::replace(&mut self.inner, None)
+    fn remove(&mut self, _: &K) -> Option<V> {
+        self.inner.take()
     }
 
     #[inline]
@@ -108,16 +208,49 @@ where
     }
 
     #[inline]
-    fn iter(&self) -> Self::Iter {
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        if let Some(value) = self.inner.as_mut() {
+            if !func(K::default(), value) {
+                self.inner = None;
+            }
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
         Iter {
-            value: self.inner.as_ref().map(|v| (K::default(), v as *const V)),
+            value: self.inner.as_ref().map(|v| (K::default(), v)),
         }
     }
 
     #[inline]
-    fn iter_mut(&mut self) -> Self::IterMut {
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
         IterMut {
-            value: self.inner.as_mut().map(|v| (K::default(), v as *mut V)),
+            value: self.inner.as_mut().map(|v| (K::default(), v)),
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            value: self.inner.map(|v| (K::default(), v)),
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, _: K) -> Entry<'_, Self, K, V> {
+        match OptionBucket::new(&mut self.inner) {
+            OptionBucket::Some(inner) => Entry::Occupied(Occupied {
+                inner,
+                key: marker::PhantomData,
+            }),
+            OptionBucket::None(inner) => Entry::Vacant(Vacant {
+                inner,
+                key: marker::PhantomData,
+            }),
         }
     }
 }
@@ -0,0 +1,264 @@
+use crate::key::TotalKey;
+use core::array;
+use core::iter;
+
+/// The trait defining how storage works for a [`TotalMap`], which holds a
+/// value for every key.
+///
+/// # Type Arguments
+///
+/// - `K` is the key being stored.
+/// - `V` is the value being stored.
+///
+/// [`TotalMap`]: ../struct.TotalMap.html
+pub trait TotalStorage<K, V>: Sized {
+    /// Immutable iterator over storage.
+    type Iter<'this>: Clone + Iterator<Item = (K, &'this V)>
+    where
+        Self: 'this,
+        V: 'this;
+
+    /// Mutable iterator over storage.
+    type IterMut<'this>: Iterator<Item = (K, &'this mut V)>
+    where
+        Self: 'this,
+        V: 'this;
+
+    /// Owning iterator over storage.
+    type IntoIter: Iterator<Item = (K, V)>;
+
+    /// Construct the storage by calling `f` once for every key, in the same
+    /// order as the keys are visited by [`iter`].
+    ///
+    /// Construction stops at the first error returned by `f`.
+    ///
+    /// [`iter`]: #tymethod.iter
+    fn try_from_fn<F, E>(f: F) -> Result<Self, E>
+    where
+        F: FnMut(K) -> Result<V, E>;
+
+    /// This is the storage abstraction for [`TotalMap::get`](../struct.TotalMap.html#method.get).
+    fn get(&self, key: K) -> &V;
+
+    /// This is the storage abstraction for [`TotalMap::get_mut`](../struct.TotalMap.html#method.get_mut).
+    fn get_mut(&mut self, key: K) -> &mut V;
+
+    /// This is the storage abstraction for [`TotalMap::iter`](../struct.TotalMap.html#method.iter).
+    fn iter(&self) -> Self::Iter<'_>;
+
+    /// This is the storage abstraction for [`TotalMap::iter_mut`](../struct.TotalMap.html#method.iter_mut).
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+
+    /// This is the storage abstraction for [`TotalMap::into_iter`](../struct.TotalMap.html#method.into_iter).
+    fn into_iter(self) -> Self::IntoIter;
+}
+
+/// Total storage for `()` keys.
+pub struct SingletonTotalStorage<V> {
+    inner: V,
+}
+
+impl<V> TotalStorage<(), V> for SingletonTotalStorage<V> {
+    type Iter<'this>
+        = iter::Once<((), &'this V)>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = iter::Once<((), &'this mut V)>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = iter::Once<((), V)>;
+
+    #[inline]
+    fn try_from_fn<F, E>(mut f: F) -> Result<Self, E>
+    where
+        F: FnMut(()) -> Result<V, E>,
+    {
+        Ok(SingletonTotalStorage { inner: f(())? })
+    }
+
+    #[inline]
+    fn get(&self, _: ()) -> &V {
+        &self.inner
+    }
+
+    #[inline]
+    fn get_mut(&mut self, _: ()) -> &mut V {
+        &mut self.inner
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        iter::once(((), &self.inner))
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        iter::once(((), &mut self.inner))
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(((), self.inner))
+    }
+}
+
+/// Total storage for `bool` keys.
+pub struct BooleanTotalStorage<V> {
+    t: V,
+    f: V,
+}
+
+impl<V> TotalStorage<bool, V> for BooleanTotalStorage<V> {
+    type Iter<'this>
+        = array::IntoIter<(bool, &'this V), 2>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = array::IntoIter<(bool, &'this mut V), 2>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = array::IntoIter<(bool, V), 2>;
+
+    #[inline]
+    fn try_from_fn<F, E>(mut f: F) -> Result<Self, E>
+    where
+        F: FnMut(bool) -> Result<V, E>,
+    {
+        Ok(BooleanTotalStorage {
+            t: f(true)?,
+            f: f(false)?,
+        })
+    }
+
+    #[inline]
+    fn get(&self, key: bool) -> &V {
+        if key {
+            &self.t
+        } else {
+            &self.f
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: bool) -> &mut V {
+        if key {
+            &mut self.t
+        } else {
+            &mut self.f
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        IntoIterator::into_iter([(true, &self.t), (false, &self.f)])
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IntoIterator::into_iter([(true, &mut self.t), (false, &mut self.f)])
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter([(true, self.t), (false, self.f)])
+    }
+}
+
+/// Total storage for `Option<K>` keys.
+pub struct OptionTotalStorage<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    some: K::TotalStorage,
+    none: V,
+}
+
+type Inner<K, V> = <K as TotalKey<K, V>>::TotalStorage;
+type SomeFn<'a, K, V> = fn((K, &'a V)) -> (Option<K>, &'a V);
+type SomeMutFn<'a, K, V> = fn((K, &'a mut V)) -> (Option<K>, &'a mut V);
+type SomeIntoFn<K, V> = fn((K, V)) -> (Option<K>, V);
+
+impl<K, V> TotalStorage<Option<K>, V> for OptionTotalStorage<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Iter<'this>
+        = iter::Chain<
+        iter::Map<<Inner<K, V> as TotalStorage<K, V>>::Iter<'this>, SomeFn<'this, K, V>>,
+        iter::Once<(Option<K>, &'this V)>,
+    >
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = iter::Chain<
+        iter::Map<<Inner<K, V> as TotalStorage<K, V>>::IterMut<'this>, SomeMutFn<'this, K, V>>,
+        iter::Once<(Option<K>, &'this mut V)>,
+    >
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = iter::Chain<
+        iter::Map<<Inner<K, V> as TotalStorage<K, V>>::IntoIter, SomeIntoFn<K, V>>,
+        iter::Once<(Option<K>, V)>,
+    >;
+
+    #[inline]
+    fn try_from_fn<F, E>(mut f: F) -> Result<Self, E>
+    where
+        F: FnMut(Option<K>) -> Result<V, E>,
+    {
+        Ok(OptionTotalStorage {
+            some: TotalStorage::try_from_fn(|k| f(Some(k)))?,
+            none: f(None)?,
+        })
+    }
+
+    #[inline]
+    fn get(&self, key: Option<K>) -> &V {
+        match key {
+            Some(key) => self.some.get(key),
+            None => &self.none,
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: Option<K>) -> &mut V {
+        match key {
+            Some(key) => self.some.get_mut(key),
+            None => &mut self.none,
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let some: SomeFn<'_, K, V> = |(k, v)| (Some(k), v);
+        self.some
+            .iter()
+            .map(some)
+            .chain(iter::once((None, &self.none)))
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let some: SomeMutFn<'_, K, V> = |(k, v)| (Some(k), v);
+        self.some
+            .iter_mut()
+            .map(some)
+            .chain(iter::once((None, &mut self.none)))
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let some: SomeIntoFn<K, V> = |(k, v)| (Some(k), v);
+        self.some
+            .into_iter()
+            .map(some)
+            .chain(iter::once((None, self.none)))
+    }
+}
@@ -0,0 +1,538 @@
+use crate::key::{Key, Product};
+use crate::map::{CapacityError, Entry, OccupiedEntry, VacantEntry};
+use crate::storage::Storage;
+
+/// The storage of the tail of a product key, which is the value stored for
+/// each head.
+type Inner<K, V> = <<K as Product>::Tail as Key<<K as Product>::Tail, V>>::Storage;
+
+/// The storage of the head of a product key.
+type Outer<K, V> = <<K as Product>::Head as Key<<K as Product>::Head, Inner<K, V>>>::Storage;
+
+type InnerIter<'a, K, V> = <Inner<K, V> as Storage<<K as Product>::Tail, V>>::Iter<'a>;
+type InnerIterMut<'a, K, V> = <Inner<K, V> as Storage<<K as Product>::Tail, V>>::IterMut<'a>;
+type InnerIntoIter<K, V> = <Inner<K, V> as Storage<<K as Product>::Tail, V>>::IntoIter;
+
+/// Storage for keys which are the product of two keys, like tuples and
+/// structs.
+///
+/// Values are stored in the storage of the tail of the key, which is in turn
+/// stored in the storage of the head of the key. So looking up a key only
+/// involves the lookups of its parts.
+///
+/// Keys are split into their parts by value, so keys which aren't `Copy` are
+/// cloned when they are looked up, and the head of the key is cloned for every
+/// key which is produced while iterating.
+pub struct TupleStorage<K, V>
+where
+    K: Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+{
+    outer: Outer<K, V>,
+    len: usize,
+}
+
+impl<K, V> Clone for TupleStorage<K, V>
+where
+    K: Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    Outer<K, V>: Clone,
+{
+    fn clone(&self) -> Self {
+        TupleStorage {
+            outer: self.outer.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<K, V> Default for TupleStorage<K, V>
+where
+    K: Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+{
+    fn default() -> Self {
+        TupleStorage {
+            outer: Default::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V> PartialEq for TupleStorage<K, V>
+where
+    K: Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        // Storages of tails can be left behind empty by the entry API, so
+        // compare the entries rather than the storages.
+        self.len == other.len && self.iter().all(|(k, v)| other.get(&k) == Some(v))
+    }
+}
+
+impl<K, V> Eq for TupleStorage<K, V>
+where
+    K: Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: Eq,
+{
+}
+
+pub struct Iter<'a, K, V>
+where
+    K: 'a + Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: 'a,
+{
+    outer: <Outer<K, V> as Storage<K::Head, Inner<K, V>>>::Iter<'a>,
+    inner: Option<(K::Head, InnerIter<'a, K, V>)>,
+}
+
+impl<'a, K, V> Clone for Iter<'a, K, V>
+where
+    K: 'a + Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: 'a,
+{
+    fn clone(&self) -> Self {
+        Iter {
+            outer: self.outer.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: 'a + Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: 'a,
+{
+    type Item = (K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((head, inner)) = &mut self.inner {
+                if let Some((tail, value)) = inner.next() {
+                    return Some((K::join(head.clone(), tail), value));
+                }
+            }
+
+            let (head, inner) = self.outer.next()?;
+            self.inner = Some((head, inner.iter()));
+        }
+    }
+}
+
+pub struct IterMut<'a, K, V>
+where
+    K: 'a + Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: 'a,
+{
+    outer: <Outer<K, V> as Storage<K::Head, Inner<K, V>>>::IterMut<'a>,
+    inner: Option<(K::Head, InnerIterMut<'a, K, V>)>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V>
+where
+    K: 'a + Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: 'a,
+{
+    type Item = (K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((head, inner)) = &mut self.inner {
+                if let Some((tail, value)) = inner.next() {
+                    return Some((K::join(head.clone(), tail), value));
+                }
+            }
+
+            let (head, inner) = self.outer.next()?;
+            self.inner = Some((head, inner.iter_mut()));
+        }
+    }
+}
+
+pub struct IntoIter<K, V>
+where
+    K: Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+{
+    outer: <Outer<K, V> as Storage<K::Head, Inner<K, V>>>::IntoIter,
+    inner: Option<(K::Head, InnerIntoIter<K, V>)>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V>
+where
+    K: Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((head, inner)) = &mut self.inner {
+                if let Some((tail, value)) = inner.next() {
+                    return Some((K::join(head.clone(), tail), value));
+                }
+            }
+
+            let (head, inner) = self.outer.next()?;
+            self.inner = Some((head, inner.into_iter()));
+        }
+    }
+}
+
+pub struct Occupied<'a, K, V>
+where
+    K: 'a + Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: 'a,
+{
+    head: K::Head,
+    inner: <Inner<K, V> as Storage<K::Tail, V>>::Occupied<'a>,
+    len: &'a mut usize,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
+where
+    K: 'a + Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: 'a,
+{
+    #[inline]
+    fn key(&self) -> K {
+        K::join(self.head.clone(), self.inner.key())
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.insert(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        *self.len -= 1;
+        self.inner.remove()
+    }
+}
+
+pub enum Vacant<'a, K, V>
+where
+    K: 'a + Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: 'a,
+{
+    /// There is no storage for the head of the key yet.
+    Outer {
+        tail: K::Tail,
+        outer: <Outer<K, V> as Storage<K::Head, Inner<K, V>>>::Vacant<'a>,
+        len: &'a mut usize,
+    },
+    /// There is storage for the head of the key, but not for its tail.
+    Inner {
+        head: K::Head,
+        inner: <Inner<K, V> as Storage<K::Tail, V>>::Vacant<'a>,
+        len: &'a mut usize,
+    },
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
+where
+    K: 'a + Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+    V: 'a,
+{
+    #[inline]
+    fn key(&self) -> K {
+        match self {
+            Vacant::Outer { tail, outer, .. } => K::join(outer.key(), tail.clone()),
+            Vacant::Inner { head, inner, .. } => K::join(head.clone(), inner.key()),
+        }
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        match self {
+            Vacant::Outer { tail, outer, len } => {
+                *len += 1;
+
+                match outer.insert(Inner::<K, V>::default()).entry(tail) {
+                    Entry::Vacant(inner) => inner.insert(value),
+                    Entry::Occupied(..) => unreachable!("storage is empty"),
+                }
+            }
+            Vacant::Inner { inner, len, .. } => {
+                *len += 1;
+                inner.insert(value)
+            }
+        }
+    }
+
+    #[inline]
+    fn try_insert(self, value: V) -> Result<&'a mut V, CapacityError<K, V>> {
+        match self {
+            Vacant::Outer { tail, outer, len } => {
+                // Like `TupleStorage::try_insert`, the storage for the tail
+                // only goes into the storage of the head once it holds the
+                // value, so that neither is left changed if either is full.
+                let mut inner = Inner::<K, V>::default();
+
+                if let Err(error) = inner.try_insert(tail.clone(), value) {
+                    let (tail, value) = error.into_inner();
+                    return Err(CapacityError::new(K::join(outer.key(), tail), value));
+                }
+
+                let inner = match outer.try_insert(inner) {
+                    Ok(inner) => inner,
+                    Err(error) => {
+                        let (head, inner) = error.into_inner();
+
+                        return match inner.into_iter().next() {
+                            Some((tail, value)) => {
+                                Err(CapacityError::new(K::join(head, tail), value))
+                            }
+                            None => unreachable!("storage holds the value"),
+                        };
+                    }
+                };
+
+                *len += 1;
+
+                match inner.get_mut(&tail) {
+                    Some(value) => Ok(value),
+                    None => unreachable!("storage holds the value"),
+                }
+            }
+            Vacant::Inner { head, inner, len } => {
+                let value = inner.try_insert(value).map_err(|error| {
+                    let (tail, value) = error.into_inner();
+                    CapacityError::new(K::join(head, tail), value)
+                })?;
+
+                *len += 1;
+                Ok(value)
+            }
+        }
+    }
+}
+
+impl<K, V> Storage<K, V> for TupleStorage<K, V>
+where
+    K: Product,
+    K::Head: Key<K::Head, Inner<K, V>>,
+    K::Tail: Key<K::Tail, V>,
+{
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        Self: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<K, V>;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        Self: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (head, tail) = key.split();
+        let old = self.outer.entry(head).or_default().insert(tail, value);
+
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    #[inline]
+    fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<K, V>> {
+        let (head, tail) = key.split();
+
+        if let Some(inner) = self.outer.get_mut(&head) {
+            let old = inner.try_insert(tail, value).map_err(|error| {
+                let (tail, value) = error.into_inner();
+                CapacityError::new(K::join(head, tail), value)
+            })?;
+
+            if old.is_none() {
+                self.len += 1;
+            }
+
+            return Ok(old);
+        }
+
+        // The storage for the tail is only added to the storage of the head
+        // once the value is in it, so that neither is left changed if either
+        // of them is full.
+        let mut inner = Inner::<K, V>::default();
+
+        if let Err(error) = inner.try_insert(tail, value) {
+            let (tail, value) = error.into_inner();
+            return Err(CapacityError::new(K::join(head, tail), value));
+        }
+
+        if let Err(error) = self.outer.try_insert(head, inner) {
+            let (head, inner) = error.into_inner();
+
+            return match inner.into_iter().next() {
+                Some((tail, value)) => Err(CapacityError::new(K::join(head, tail), value)),
+                None => unreachable!("storage holds the value"),
+            };
+        }
+
+        self.len += 1;
+        Ok(None)
+    }
+
+    #[inline]
+    fn get(&self, key: &K) -> Option<&V> {
+        let (head, tail) = key.clone().split();
+        self.outer.get(&head)?.get(&tail)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let (head, tail) = key.clone().split();
+        self.outer.get_mut(&head)?.get_mut(&tail)
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let (head, tail) = key.clone().split();
+        let inner = self.outer.get_mut(&head)?;
+        let value = inner.remove(&tail)?;
+
+        if inner.is_empty() {
+            self.outer.remove(&head);
+        }
+
+        self.len -= 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.outer.clear();
+        self.len = 0;
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        let len = &mut self.len;
+
+        self.outer.retain(|head, inner| {
+            inner.retain(|tail, value| {
+                let keep = func(K::join(head.clone(), tail), value);
+
+                if !keep {
+                    *len -= 1;
+                }
+
+                keep
+            });
+
+            !inner.is_empty()
+        });
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            outer: self.outer.iter(),
+            inner: None,
+        }
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            outer: self.outer.iter_mut(),
+            inner: None,
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            outer: self.outer.into_iter(),
+            inner: None,
+        }
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        let (head, tail) = key.split();
+        let len = &mut self.len;
+
+        match self.outer.entry(head.clone()) {
+            Entry::Occupied(outer) => match outer.into_mut().entry(tail) {
+                Entry::Occupied(inner) => Entry::Occupied(Occupied { head, inner, len }),
+                Entry::Vacant(inner) => Entry::Vacant(Vacant::Inner { head, inner, len }),
+            },
+            Entry::Vacant(outer) => Entry::Vacant(Vacant::Outer { tail, outer, len }),
+        }
+    }
+}
@@ -0,0 +1,814 @@
+//! Contains the fixed `TotalMap` implementation.
+use crate::{
+    key::{Key, TotalKey},
+    map::Map,
+    storage::TotalStorage,
+};
+use core::convert::{Infallible, TryFrom};
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+/// A fixed map which holds a value for every possible key.
+///
+/// Since no key can be missing, values are stored directly instead of as
+/// `Option<V>`, and lookups can't fail.
+///
+/// # Examples
+///
+/// ```rust
+/// use fixed_map::{Key, TotalMap};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Part {
+///     One,
+///     Two,
+/// }
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Key {
+///     Simple,
+///     Composite(Part),
+///     Singleton(()),
+///     Option(Option<Part>),
+///     Boolean(bool),
+/// }
+///
+/// let mut map = TotalMap::from_fn(|key| match key {
+///     Key::Simple => 1,
+///     Key::Composite(_) => 2,
+///     Key::Singleton(()) => 3,
+///     Key::Option(None) => 4,
+///     Key::Option(Some(_)) => 5,
+///     Key::Boolean(b) => 6 + b as u32,
+/// });
+///
+/// map[Key::Boolean(false)] += 10;
+///
+/// assert_eq!(map[Key::Simple], 1);
+/// assert_eq!(map[Key::Composite(Part::Two)], 2);
+/// assert_eq!(map[Key::Option(None)], 4);
+/// assert_eq!(map[Key::Boolean(true)], 7);
+/// assert_eq!(map[Key::Boolean(false)], 16);
+/// ```
+pub struct TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    storage: K::TotalStorage,
+}
+
+impl<K, V> TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    /// Creates a `TotalMap` by calling `f` with every key, in the same order
+    /// as they are visited by [`iter`].
+    ///
+    /// [`iter`]: #method.iter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let map = TotalMap::from_fn(|k| k == Key::One);
+    /// assert_eq!(map[Key::One], true);
+    /// assert_eq!(map[Key::Two], false);
+    /// ```
+    #[inline]
+    pub fn from_fn<F>(mut f: F) -> TotalMap<K, V>
+    where
+        F: FnMut(K) -> V,
+    {
+        match K::TotalStorage::try_from_fn(|k| Ok::<_, Infallible>(f(k))) {
+            Ok(storage) => TotalMap { storage },
+            Err(e) => match e {},
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let map = TotalMap::from_fn(|k| match k {
+    ///     Key::One => "a",
+    ///     Key::Two => "b",
+    /// });
+    ///
+    /// assert_eq!(map.get(Key::Two), &"b");
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> &V {
+        self.storage.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = TotalMap::from_fn(|_| 0);
+    /// *map.get_mut(Key::One) += 1;
+    ///
+    /// assert_eq!(map[Key::One], 1);
+    /// assert_eq!(map[Key::Two], 0);
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> &mut V {
+        self.storage.get_mut(key)
+    }
+
+    /// Replaces the value corresponding to the key, returning the old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = TotalMap::from_fn(|_| 0);
+    /// assert_eq!(map.replace(Key::One, 1), 0);
+    /// assert_eq!(map[Key::One], 1);
+    /// ```
+    #[inline]
+    pub fn replace(&mut self, key: K, value: V) -> V {
+        core::mem::replace(self.storage.get_mut(key), value)
+    }
+
+    /// An iterator visiting all key-value pairs in the order of the keys.
+    /// The iterator element type is `(K, &'a V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let map = TotalMap::from_fn(|k| k == Key::One);
+    ///
+    /// assert_eq!(
+    ///     map.iter().collect::<Vec<_>>(),
+    ///     vec![(Key::One, &true), (Key::Two, &false)]
+    /// );
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            iter: self.storage.iter(),
+        }
+    }
+
+    /// An iterator visiting all key-value pairs in the order of the keys,
+    /// with mutable references to the values.
+    /// The iterator element type is `(K, &'a mut V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = TotalMap::<Key, u32>::from_fn(|_| 1);
+    ///
+    /// for (_, v) in map.iter_mut() {
+    ///     *v *= 2;
+    /// }
+    ///
+    /// assert_eq!(map.values().copied().collect::<Vec<_>>(), vec![2, 2]);
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            iter: self.storage.iter_mut(),
+        }
+    }
+
+    /// An iterator visiting all keys in order.
+    /// The iterator element type is `K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let map = TotalMap::<Key, ()>::from_fn(|_| ());
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), vec![Key::One, Key::Two]);
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            iter: self.storage.iter(),
+        }
+    }
+
+    /// An iterator visiting all values in the order of their keys.
+    /// The iterator element type is `&'a V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let map = TotalMap::from_fn(|k| match k {
+    ///     Key::One => 1,
+    ///     Key::Two => 2,
+    /// });
+    ///
+    /// assert_eq!(map.values().collect::<Vec<_>>(), vec![&1, &2]);
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            iter: self.storage.iter(),
+        }
+    }
+
+    /// A mutable iterator visiting all values in the order of their keys.
+    /// The iterator element type is `&'a mut V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = TotalMap::from_fn(|_| 1);
+    ///
+    /// for v in map.values_mut() {
+    ///     *v += 1;
+    /// }
+    ///
+    /// assert_eq!(map[Key::One], 2);
+    /// assert_eq!(map[Key::Two], 2);
+    /// ```
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut {
+            iter: self.storage.iter_mut(),
+        }
+    }
+
+    /// Creates a new `TotalMap` by applying `f` to every key-value pair.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let map = TotalMap::from_fn(|k| match k {
+    ///     Key::One => 1,
+    ///     Key::Two => 2,
+    /// });
+    ///
+    /// let map = map.map(|_, v| v.to_string());
+    /// assert_eq!(map[Key::One], "1");
+    /// assert_eq!(map[Key::Two], "2");
+    /// ```
+    #[inline]
+    pub fn map<U, F>(self, mut f: F) -> TotalMap<K, U>
+    where
+        K: TotalKey<K, U>,
+        F: FnMut(K, V) -> U,
+    {
+        let mut iter = self.storage.into_iter();
+
+        TotalMap::from_fn(|_| {
+            let (k, v) = iter
+                .next()
+                .expect("storage visits every key in the same order");
+            f(k, v)
+        })
+    }
+}
+
+/// Creates a [`TotalMap`] from an exhaustive list of `key => value` arms.
+///
+/// The arms form the body of a `match` over every key, so the compiler checks
+/// that all keys are covered. Values are evaluated once for every key they
+/// match, so patterns which match several keys are allowed.
+///
+/// [`TotalMap`]: struct.TotalMap.html
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{total_map, Key, TotalMap};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Dir {
+///     North,
+///     East,
+///     South,
+///     West,
+/// }
+///
+/// let map: TotalMap<Dir, (i32, i32)> = total_map! {
+///     Dir::North => (0, -1),
+///     Dir::East => (1, 0),
+///     Dir::South => (0, 1),
+///     Dir::West => (-1, 0),
+/// };
+///
+/// assert_eq!(map[Dir::East], (1, 0));
+/// ```
+#[macro_export]
+macro_rules! total_map {
+    ($($key:pat => $value:expr),* $(,)?) => {
+        $crate::TotalMap::from_fn(|key| match key {
+            $($key => $value,)*
+        })
+    };
+}
+
+impl<K, V> Clone for TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+    V: Clone,
+{
+    fn clone(&self) -> TotalMap<K, V> {
+        TotalMap::from_fn(|k| self.get(k).clone())
+    }
+}
+
+impl<K, V> Default for TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+    V: Default,
+{
+    fn default() -> Self {
+        TotalMap::from_fn(|_| V::default())
+    }
+}
+
+impl<K, V> fmt::Debug for TotalMap<K, V>
+where
+    K: TotalKey<K, V> + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug_map = f.debug_map();
+        for (k, v) in self.iter() {
+            debug_map.entry(&k, v);
+        }
+        debug_map.finish()
+    }
+}
+
+impl<K, V> PartialEq for TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.values().eq(other.values())
+    }
+}
+
+impl<K, V> Eq for TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+    V: Eq,
+{
+}
+
+impl<K, V> Index<K> for TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let map = TotalMap::from_fn(|_| "a");
+    /// assert_eq!(map[Key::One], "a");
+    /// ```
+    #[inline]
+    fn index(&self, key: K) -> &Self::Output {
+        self.storage.get(key)
+    }
+}
+
+impl<K, V> IndexMut<K> for TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    /// Returns a mutable reference to the value corresponding to the supplied
+    /// key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = TotalMap::from_fn(|_| 1);
+    /// map[Key::Two] = 2;
+    /// assert_eq!(map[Key::Two], 2);
+    /// ```
+    #[inline]
+    fn index_mut(&mut self, key: K) -> &mut Self::Output {
+        self.storage.get_mut(key)
+    }
+}
+
+impl<K, V> TryFrom<Map<K, V>> for TotalMap<K, V>
+where
+    K: Key<K, V> + TotalKey<K, V>,
+{
+    type Error = MissingKey<K>;
+
+    /// Converts a `Map` into a `TotalMap`, failing with the first key that
+    /// has no value in the `Map`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map, TotalMap};
+    /// use std::convert::TryFrom;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::One, 1);
+    ///
+    /// let error = TotalMap::try_from(map.clone()).unwrap_err();
+    /// assert_eq!(error.key(), Key::Two);
+    ///
+    /// map.insert(Key::Two, 2);
+    /// let total = TotalMap::try_from(map).unwrap();
+    /// assert_eq!(total[Key::Two], 2);
+    /// ```
+    fn try_from(mut map: Map<K, V>) -> Result<Self, Self::Error> {
+        let storage =
+            K::TotalStorage::try_from_fn(|key| map.remove(key).ok_or(MissingKey { key }))?;
+        Ok(TotalMap { storage })
+    }
+}
+
+impl<K, V> From<TotalMap<K, V>> for Map<K, V>
+where
+    K: Key<K, V> + TotalKey<K, V>,
+{
+    /// Converts a `TotalMap` into a `Map` which has a value for every key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map, TotalMap};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let map = Map::from(TotalMap::from_fn(|_| 1));
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get(Key::Two), Some(&1));
+    /// ```
+    fn from(map: TotalMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V> IntoIterator for TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    /// Creates a consuming iterator visiting all key-value pairs in the order
+    /// of the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, TotalMap};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let map = TotalMap::from_fn(|k| k == Key::Two);
+    ///
+    /// assert_eq!(
+    ///     map.into_iter().collect::<Vec<_>>(),
+    ///     vec![(Key::One, false), (Key::Two, true)]
+    /// );
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: self.storage.into_iter(),
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a> IntoIterator for &'a TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Item = (K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: 'a, V: 'a> IntoIterator for &'a mut TotalMap<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Item = (K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// The error returned when converting a `Map` into a [`TotalMap`] fails
+/// because a key has no value.
+///
+/// [`TotalMap`]: struct.TotalMap.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingKey<K> {
+    key: K,
+}
+
+impl<K> MissingKey<K>
+where
+    K: Copy,
+{
+    /// The first key which has no value.
+    pub fn key(&self) -> K {
+        self.key
+    }
+}
+
+impl<K> fmt::Display for MissingKey<K>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "missing value for key {:?}", self.key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K> std::error::Error for MissingKey<K> where K: fmt::Debug {}
+
+/// An iterator over the entries of a `TotalMap`.
+///
+/// This `struct` is created by the [`iter`] method on [`TotalMap`].
+/// See its documentation for more.
+///
+/// [`iter`]: struct.TotalMap.html#method.iter
+/// [`TotalMap`]: struct.TotalMap.html
+pub struct Iter<'a, K: 'a, V: 'a>
+where
+    K: TotalKey<K, V>,
+{
+    iter: <K::TotalStorage as TotalStorage<K, V>>::Iter<'a>,
+}
+
+impl<'a, K: 'a, V: 'a> Clone for Iter<'a, K, V>
+where
+    K: TotalKey<K, V>,
+{
+    #[inline]
+    fn clone(&self) -> Iter<'a, K, V> {
+        Iter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a> Iterator for Iter<'a, K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Item = (K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// A mutable iterator over the entries of a `TotalMap`.
+///
+/// This `struct` is created by the [`iter_mut`] method on [`TotalMap`].
+/// See its documentation for more.
+///
+/// [`iter_mut`]: struct.TotalMap.html#method.iter_mut
+/// [`TotalMap`]: struct.TotalMap.html
+pub struct IterMut<'a, K: 'a, V: 'a>
+where
+    K: TotalKey<K, V>,
+{
+    iter: <K::TotalStorage as TotalStorage<K, V>>::IterMut<'a>,
+}
+
+impl<'a, K: 'a, V: 'a> Iterator for IterMut<'a, K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Item = (K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// An iterator over the keys of a `TotalMap`.
+///
+/// This `struct` is created by the [`keys`] method on [`TotalMap`].
+/// See its documentation for more.
+///
+/// [`keys`]: struct.TotalMap.html#method.keys
+/// [`TotalMap`]: struct.TotalMap.html
+pub struct Keys<'a, K: 'a, V: 'a>
+where
+    K: TotalKey<K, V>,
+{
+    iter: <K::TotalStorage as TotalStorage<K, V>>::Iter<'a>,
+}
+
+impl<'a, K: 'a, V: 'a> Iterator for Keys<'a, K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, _)| k)
+    }
+}
+
+/// An iterator over the values of a `TotalMap`.
+///
+/// This `struct` is created by the [`values`] method on [`TotalMap`].
+/// See its documentation for more.
+///
+/// [`values`]: struct.TotalMap.html#method.values
+/// [`TotalMap`]: struct.TotalMap.html
+pub struct Values<'a, K: 'a, V: 'a>
+where
+    K: TotalKey<K, V>,
+{
+    iter: <K::TotalStorage as TotalStorage<K, V>>::Iter<'a>,
+}
+
+impl<'a, K: 'a, V: 'a> Iterator for Values<'a, K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Item = &'a V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, v)| v)
+    }
+}
+
+/// A mutable iterator over the values of a `TotalMap`.
+///
+/// This `struct` is created by the [`values_mut`] method on [`TotalMap`].
+/// See its documentation for more.
+///
+/// [`values_mut`]: struct.TotalMap.html#method.values_mut
+/// [`TotalMap`]: struct.TotalMap.html
+pub struct ValuesMut<'a, K: 'a, V: 'a>
+where
+    K: TotalKey<K, V>,
+{
+    iter: <K::TotalStorage as TotalStorage<K, V>>::IterMut<'a>,
+}
+
+impl<'a, K: 'a, V: 'a> Iterator for ValuesMut<'a, K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Item = &'a mut V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, v)| v)
+    }
+}
+
+/// An owning iterator over the entries of a `TotalMap`.
+///
+/// This `struct` is created by the [`into_iter`] method on [`TotalMap`]
+/// (provided by the `IntoIterator` trait). See its documentation for more.
+///
+/// [`into_iter`]: struct.TotalMap.html#method.into_iter
+/// [`TotalMap`]: struct.TotalMap.html
+pub struct IntoIter<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    iter: <K::TotalStorage as TotalStorage<K, V>>::IntoIter,
+}
+
+impl<K, V> Iterator for IntoIter<K, V>
+where
+    K: TotalKey<K, V>,
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
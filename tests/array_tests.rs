@@ -0,0 +1,124 @@
+use fixed_map::key::Enumerable;
+use fixed_map::map::Entry;
+#[cfg(feature = "alloc")]
+use fixed_map::Set;
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Dir {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[test]
+fn test_pair_of_enums() {
+    let mut map = Map::new();
+
+    assert_eq!(map.insert([Dir::North, Dir::East], 1), None);
+    assert_eq!(map.insert([Dir::South, Dir::West], 2), None);
+    assert_eq!(map.insert([Dir::North, Dir::East], 3), Some(1));
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get([Dir::North, Dir::East]), Some(&3));
+    assert_eq!(map.get([Dir::East, Dir::North]), None);
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![([Dir::North, Dir::East], &3), ([Dir::South, Dir::West], &2)]
+    );
+
+    assert_eq!(map.remove([Dir::South, Dir::West]), Some(2));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_entry() {
+    let mut map = Map::new();
+
+    *map.entry([true, false, true]).or_insert(0) += 1;
+    *map.entry([true, false, true]).or_insert(0) += 1;
+
+    match map.entry([false, false, false]) {
+        Entry::Vacant(entry) => {
+            fixed_map::map::VacantEntry::insert(entry, 5);
+        }
+        Entry::Occupied(..) => panic!("expected a vacant entry"),
+    }
+
+    assert_eq!(
+        map.into_iter().collect::<Vec<_>>(),
+        vec![([true, false, true], 2), ([false, false, false], 5)]
+    );
+}
+
+#[test]
+fn test_empty_and_long_arrays() {
+    let mut map = Map::new();
+    map.insert([], "empty");
+    assert_eq!(map.get([] as [Dir; 0]), Some(&"empty"));
+
+    #[cfg(feature = "alloc")]
+    {
+        let key = [1u32, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+        let mut other = key;
+        other[15] = 0;
+
+        let mut map = Map::new();
+        map.insert(key, 16);
+
+        assert_eq!(map.get(key), Some(&16));
+        assert_eq!(map.get(other), None);
+        assert_eq!(map.keys().collect::<Vec<_>>(), vec![key]);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hashed_elements() {
+    let mut set = Set::new();
+    set.insert([1u32, 2]);
+    set.insert([2u32, 1]);
+
+    assert!(set.contains([1, 2]));
+    assert!(set.contains([2, 1]));
+    assert!(!set.contains([1, 1]));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_enumerable() {
+    assert_eq!(<[Dir; 0]>::COUNT, 1);
+    assert_eq!(<[Dir; 2]>::COUNT, 16);
+    assert_eq!([Dir::East, Dir::West].ordinal(), 7);
+    assert_eq!(<[Dir; 2]>::from_ordinal(9), Some([Dir::South, Dir::East]));
+    assert_eq!(<[Dir; 2]>::from_ordinal(16), None);
+
+    let mut map = Map::new();
+
+    for key in <[bool; 3]>::all().rev() {
+        map.insert(key, key.ordinal());
+    }
+
+    assert!(map.iter().map(|(_, &ordinal)| ordinal).eq(0..8));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_owned_elements() {
+    let key = [String::from("foo"), String::from("bar")];
+    let mut map = Map::new();
+
+    map.insert(key.clone(), 1);
+    map.insert([String::from("bar"), String::from("foo")], 2);
+
+    assert_eq!(map.get(&key), Some(&1));
+    assert_eq!(map.get([String::from("foo"), String::from("foo")]), None);
+    assert_eq!(map.remove(&key), Some(1));
+    assert_eq!(
+        map.into_iter().collect::<Vec<_>>(),
+        vec![([String::from("bar"), String::from("foo")], 2)]
+    );
+}
@@ -0,0 +1,119 @@
+use fixed_map::{Key, Map, Set};
+use std::mem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(bitset)]
+enum Small {
+    First,
+    Second,
+    Third,
+}
+
+macro_rules! large {
+    ($name:ident, $($member:ident),*) => {
+        #[allow(unused)]
+        #[derive(Debug, Clone, Copy, PartialEq, Key)]
+        #[key(bitset)]
+        enum $name {
+            $($member,)*
+        }
+    };
+}
+
+large! {
+    Large,
+    T00, T01, T02, T03, T04, T05, T06, T07, T08, T09, T10, T11, T12, T13, T14, T15,
+    T16, T17, T18, T19, T20, T21, T22, T23, T24, T25, T26, T27, T28, T29, T30, T31,
+    T32, T33, T34, T35, T36, T37, T38, T39, T40, T41, T42, T43, T44, T45, T46, T47,
+    T48, T49, T50, T51, T52, T53, T54, T55, T56, T57, T58, T59, T60, T61, T62, T63
+}
+
+large! {
+    Huge,
+    T000, T001, T002, T003, T004, T005, T006, T007, T008, T009, T010, T011, T012, T013, T014, T015,
+    T016, T017, T018, T019, T020, T021, T022, T023, T024, T025, T026, T027, T028, T029, T030, T031,
+    T032, T033, T034, T035, T036, T037, T038, T039, T040, T041, T042, T043, T044, T045, T046, T047,
+    T048, T049, T050, T051, T052, T053, T054, T055, T056, T057, T058, T059, T060, T061, T062, T063,
+    T064, T065, T066, T067, T068, T069, T070, T071, T072, T073, T074, T075, T076, T077, T078, T079,
+    T080, T081, T082, T083, T084, T085, T086, T087, T088, T089, T090, T091, T092, T093, T094, T095,
+    T096, T097, T098, T099, T100, T101, T102, T103, T104, T105, T106, T107, T108, T109, T110, T111,
+    T112, T113, T114, T115, T116, T117, T118, T119, T120, T121, T122, T123, T124, T125, T126, T127,
+    T128, T129
+}
+
+#[test]
+fn test_size() {
+    assert_eq!(mem::size_of::<Set<Small>>(), mem::size_of::<u8>());
+    assert_eq!(mem::size_of::<Set<Large>>(), mem::size_of::<u64>());
+    assert_eq!(mem::size_of::<Set<Huge>>(), mem::size_of::<[u64; 3]>());
+}
+
+#[test]
+fn test_set() {
+    let mut set = Set::new();
+    assert!(set.is_empty());
+
+    assert!(set.insert(Small::Third));
+    assert!(set.insert(Small::First));
+    assert!(!set.insert(Small::First));
+
+    assert!(set.contains(Small::First));
+    assert!(!set.contains(Small::Second));
+    assert!(set.contains(Small::Third));
+    assert_eq!(set.len(), 2);
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![Small::First, Small::Third]
+    );
+
+    let copy = set.clone();
+    assert_eq!(copy, set);
+
+    assert!(set.remove(Small::First));
+    assert!(!set.remove(Small::First));
+    assert_ne!(copy, set);
+
+    set.clear();
+    assert!(set.is_empty());
+}
+
+#[test]
+fn test_large_set() {
+    let mut set = Set::new();
+    set.insert(Large::T63);
+    set.insert(Large::T00);
+    set.insert(Large::T31);
+
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![Large::T00, Large::T31, Large::T63]
+    );
+
+    set.retain(|k| k != Large::T31);
+    assert_eq!(
+        set.into_iter().collect::<Vec<_>>(),
+        vec![Large::T00, Large::T63]
+    );
+
+    let mut set = Set::new();
+    set.insert(Huge::T129);
+    set.insert(Huge::T064);
+    set.insert(Huge::T001);
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(Huge::T064));
+    assert!(!set.contains(Huge::T063));
+
+    assert_eq!(
+        set.drain().collect::<Vec<_>>(),
+        vec![Huge::T001, Huge::T064, Huge::T129]
+    );
+    assert!(set.is_empty());
+}
+
+#[test]
+fn test_map() {
+    let mut map = Map::new();
+    map.insert(Small::Second, 2);
+    assert_eq!(map.get(Small::Second), Some(&2));
+    assert_eq!(map.get(Small::First), None);
+}
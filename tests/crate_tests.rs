@@ -0,0 +1,169 @@
+// The crate under another name, like a dependency which is renamed in
+// `Cargo.toml`.
+extern crate fixed_map as renamed;
+
+// Shadows the crate, so that any relative `fixed_map::` path in the generated
+// code fails to resolve.
+#[allow(dead_code)]
+mod fixed_map {}
+
+mod facade {
+    pub use ::fixed_map as maps;
+}
+
+// Shadows the prelude, so that any generated code which relies on the names
+// in it fails to compile.
+#[allow(dead_code, unused_macros)]
+mod shadowed {
+    use renamed::Key;
+
+    type Result<T> = core::result::Result<T, ()>;
+    type Option = ();
+    struct Some;
+    struct None;
+    struct Ok;
+    struct Err;
+    trait Clone {}
+    trait Copy {}
+    trait Default {}
+    trait Iterator {}
+    trait FnMut {}
+    trait From {}
+
+    macro_rules! unreachable {
+        ($($tt:tt)*) => {
+            compile_error!("the prelude `unreachable!` is shadowed")
+        };
+    }
+
+    #[derive(::core::clone::Clone, ::core::marker::Copy, Key)]
+    pub enum Part {
+        First,
+        Second,
+    }
+
+    #[derive(::core::clone::Clone, ::core::marker::Copy, Key)]
+    #[key(bitset)]
+    pub enum Flag {
+        First,
+        Second,
+    }
+
+    #[derive(::core::clone::Clone, ::core::marker::Copy, Key)]
+    pub enum Shadowed {
+        First,
+        Second(Part),
+        Third(bool, Part),
+        #[key(inline = 2)]
+        Fourth(u32),
+    }
+
+    #[derive(::core::clone::Clone, ::core::marker::Copy, Key)]
+    pub struct Pair {
+        pub part: Part,
+        pub flag: bool,
+    }
+}
+
+use renamed::key::Enumerable;
+use renamed::{Key, Map, Set, TotalMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Absolute {
+    First,
+    Second(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+#[key(crate = renamed)]
+enum Renamed {
+    First,
+    Second(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+#[key(crate = facade::maps, bitset)]
+enum Reexported {
+    First,
+    Second,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+#[key(crate = crate::facade::maps)]
+struct Cell {
+    key: Renamed,
+    top: bool,
+}
+
+#[test]
+fn test_absolute_path() {
+    let mut map = Map::new();
+    map.insert(Absolute::Second(true), 1);
+    assert_eq!(map.get(Absolute::Second(true)), Some(&1));
+    assert_eq!(Absolute::COUNT, 3);
+}
+
+#[test]
+fn test_renamed() {
+    let mut map = Map::new();
+    map.insert(Renamed::First, 1);
+    map.insert(Renamed::Second(false), 2);
+    assert_eq!(map.get(Renamed::Second(false)), Some(&2));
+    assert_eq!(Renamed::Second(true).ordinal(), 1);
+}
+
+#[test]
+fn test_reexported() {
+    let mut set = Set::new();
+    set.insert(Reexported::Second);
+    assert!(set.contains(Reexported::Second));
+    assert!(!set.contains(Reexported::First));
+    assert_eq!(std::mem::size_of_val(&set), 1);
+
+    let mut map = Map::new();
+    map.insert(
+        Cell {
+            key: Renamed::First,
+            top: true,
+        },
+        1,
+    );
+    assert_eq!(
+        map.get(Cell {
+            key: Renamed::First,
+            top: true
+        }),
+        Some(&1)
+    );
+}
+
+#[test]
+fn test_shadowed_prelude() {
+    use shadowed::{Flag, Pair, Part, Shadowed};
+
+    let mut map = Map::new();
+    map.insert(Shadowed::Second(Part::First), 1);
+    map.insert(Shadowed::Third(true, Part::Second), 2);
+    assert!(map.try_insert(Shadowed::Fourth(1), 3).is_ok());
+    assert_eq!(map.get(Shadowed::Second(Part::First)), Some(&1));
+    assert_eq!(map.iter().count(), 3);
+    assert_eq!(map.clone().len(), 3);
+
+    let mut set = Set::new();
+    set.insert(Flag::Second);
+    assert_eq!(set.iter().count(), 1);
+
+    let mut pairs = Map::new();
+    pairs.insert(
+        Pair {
+            part: Part::First,
+            flag: true,
+        },
+        1,
+    );
+    assert_eq!(pairs.len(), 1);
+
+    let total = TotalMap::from_fn(|part| matches!(part, Part::Second));
+    assert!(total[Part::Second]);
+    assert_eq!(Part::COUNT, 2);
+}
@@ -0,0 +1,182 @@
+use fixed_map::key::{Dense, Enumerable};
+use fixed_map::map::{Entry, OccupiedEntry, VacantEntry};
+use fixed_map::Map;
+#[cfg(feature = "alloc")]
+use fixed_map::{Key, Set};
+
+#[test]
+fn test_u8_keys() {
+    let mut map = Map::new();
+
+    assert_eq!(map.insert(b'a', 1), None);
+    assert_eq!(map.insert(b'z', 2), None);
+    assert_eq!(map.insert(255u8, 3), None);
+    assert_eq!(map.insert(b'a', 4), Some(1));
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(b'a'), Some(&4));
+    assert_eq!(map.get(b'b'), None);
+    assert_eq!(map.get(255), Some(&3));
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![(b'a', &4), (b'z', &2), (255, &3)]
+    );
+
+    assert_eq!(map.remove(b'z'), Some(2));
+    assert_eq!(map.remove(b'z'), None);
+    assert_eq!(map.len(), 2);
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.get(b'a'), None);
+}
+
+#[test]
+fn test_i8_keys_in_order() {
+    let mut map = Map::new();
+
+    for key in [0i8, 127, -128, -1, 1] {
+        map.insert(key, key);
+    }
+
+    assert_eq!(map.keys().collect::<Vec<_>>(), vec![-128, -1, 0, 1, 127]);
+
+    map.retain(|key, _| key >= 0);
+
+    assert_eq!(
+        map.into_iter().collect::<Vec<_>>(),
+        vec![(0, 0), (1, 1), (127, 127)]
+    );
+}
+
+#[test]
+fn test_entry() {
+    let mut map = Map::new();
+
+    *map.entry(b'x').or_insert(0) += 1;
+    *map.entry(b'x').or_insert(0) += 1;
+    assert_eq!(map.get(b'x'), Some(&2));
+
+    match map.entry(b'x') {
+        Entry::Occupied(entry) => {
+            assert_eq!(entry.key(), b'x');
+            assert_eq!(entry.remove(), 2);
+        }
+        Entry::Vacant(..) => panic!("expected an occupied entry"),
+    }
+
+    match map.entry(b'y') {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), b'y');
+            *entry.insert(5) += 1;
+        }
+        Entry::Occupied(..) => panic!("expected a vacant entry"),
+    }
+
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(b'y'), Some(&6));
+}
+
+#[test]
+fn test_eq_and_clone() {
+    let mut a = Map::new();
+    let b = Map::<u8, u32>::new();
+    a.insert(1u8, 1);
+    a.remove(1);
+
+    // An emptied storage is equal to one which has never been allocated.
+    assert_eq!(a, b);
+
+    a.insert(2, 2);
+    let c = a.clone();
+    assert_eq!(a, c);
+    assert_ne!(a, b);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_dense_wide_integers() {
+    let mut map = Map::new();
+    map.insert(Dense(u16::MAX), 1);
+    map.insert(Dense(0u16), 2);
+
+    assert_eq!(map.get(Dense(u16::MAX)), Some(&1));
+    assert_eq!(map.get(Dense(1)), None);
+    assert_eq!(
+        map.keys().collect::<Vec<_>>(),
+        vec![Dense(0), Dense(u16::MAX)]
+    );
+
+    let mut set = Set::new();
+    set.insert(Dense(i16::MIN));
+    set.insert(Dense(-1i16));
+
+    assert!(set.contains(Dense(i16::MIN)));
+    assert!(!set.contains(Dense(i16::MAX)));
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![Dense(i16::MIN), Dense(-1)]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_dense_entry() {
+    let mut map = Map::new();
+
+    // The first entry is vacant before the slots are allocated.
+    match map.entry(Dense(0x1234u16)) {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), Dense(0x1234));
+            *entry.insert(1) += 1;
+        }
+        Entry::Occupied(..) => panic!("expected a vacant entry"),
+    }
+
+    *map.entry(Dense(0x1234)).or_insert(0) += 1;
+    *map.entry(Dense(0x4321)).or_insert(0) += 1;
+
+    match map.entry(Dense(0x1234)) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.key(), Dense(0x1234));
+            assert_eq!(entry.insert(10), 3);
+            assert_eq!(entry.remove(), 10);
+        }
+        Entry::Vacant(..) => panic!("expected an occupied entry"),
+    }
+
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(Dense(0x4321)), Some(&1));
+}
+
+#[test]
+fn test_enumerable() {
+    assert_eq!(u8::COUNT, 256);
+    assert_eq!(i8::COUNT, 256);
+    assert_eq!(Dense::<u16>::COUNT, 65536);
+    assert_eq!((-128i8).ordinal(), 0);
+    assert_eq!(i8::from_ordinal(255), Some(127));
+    assert_eq!(u8::from_ordinal(256), None);
+    assert_eq!(Dense::<i16>::from_ordinal(0), Some(Dense(i16::MIN)));
+    assert!(u8::all().eq(0..=255));
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Class {
+    Opcode(u8),
+    Wide(Dense<u16>),
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_derive() {
+    let mut map = Map::new();
+    map.insert(Class::Opcode(0x90), "nop");
+    map.insert(Class::Wide(Dense(0x0f05)), "syscall");
+
+    assert_eq!(map.get(Class::Opcode(0x90)), Some(&"nop"));
+    assert_eq!(map.get(Class::Opcode(0x91)), None);
+    assert_eq!(map.get(Class::Wide(Dense(0x0f05))), Some(&"syscall"));
+}
@@ -41,3 +41,261 @@ fn test_debug() {
 
     assert_eq!("{First: 42}", format!("{:?}", a))
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_entry() {
+    use fixed_map::Map;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Part {
+        One,
+        Two,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Composite {
+        Simple,
+        Part(Part),
+        Option(Option<Part>),
+        Boolean(bool),
+        String(&'static str),
+        Singleton(()),
+    }
+
+    let keys = [
+        Composite::Simple,
+        Composite::Part(Part::Two),
+        Composite::Option(None),
+        Composite::Option(Some(Part::One)),
+        Composite::Boolean(false),
+        Composite::String("foo"),
+        Composite::Singleton(()),
+    ];
+
+    let mut a = Map::new();
+
+    for (n, key) in keys.iter().enumerate() {
+        assert_eq!(a.entry(*key).key(), *key);
+        *a.entry(*key).or_insert(n) += 10;
+        *a.entry(*key).or_insert(n) += 10;
+    }
+
+    for (n, key) in keys.iter().enumerate() {
+        assert_eq!(a.get(*key), Some(&(n + 20)));
+    }
+
+    assert_eq!(a.get(Composite::Part(Part::One)), None);
+    assert_eq!(a.get(Composite::Boolean(true)), None);
+    assert_eq!(a.get(Composite::String("bar")), None);
+}
+
+#[test]
+fn test_iter_mut() {
+    use fixed_map::Map;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Part {
+        One,
+        Two,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Composite {
+        Simple,
+        Part(Part),
+        Option(Option<Part>),
+        Boolean(bool),
+    }
+
+    let mut a = Map::new();
+    a.insert(Composite::Simple, 1);
+    a.insert(Composite::Part(Part::Two), 2);
+    a.insert(Composite::Option(None), 3);
+    a.insert(Composite::Option(Some(Part::One)), 4);
+    a.insert(Composite::Boolean(true), 5);
+
+    for (_, v) in a.iter_mut() {
+        *v *= 10;
+    }
+
+    assert_eq!(
+        a.iter().collect::<Vec<_>>(),
+        vec![
+            (Composite::Simple, &10),
+            (Composite::Part(Part::Two), &20),
+            (Composite::Option(Some(Part::One)), &40),
+            (Composite::Option(None), &30),
+            (Composite::Boolean(true), &50),
+        ]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_into_iter() {
+    use fixed_map::{Map, Set};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Part {
+        One,
+        Two,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Composite {
+        Simple,
+        Part(Part),
+        Option(Option<Part>),
+        Number(u32),
+    }
+
+    let mut a = Map::new();
+    a.insert(Composite::Simple, String::from("simple"));
+    a.insert(Composite::Part(Part::Two), String::from("two"));
+    a.insert(Composite::Option(None), String::from("none"));
+    a.insert(Composite::Number(42), String::from("number"));
+
+    assert_eq!(
+        a.into_iter().collect::<Vec<_>>(),
+        vec![
+            (Composite::Simple, String::from("simple")),
+            (Composite::Part(Part::Two), String::from("two")),
+            (Composite::Option(None), String::from("none")),
+            (Composite::Number(42), String::from("number")),
+        ]
+    );
+
+    let mut b = Set::new();
+    b.insert(Composite::Part(Part::One));
+    b.insert(Composite::Number(7));
+
+    let mut keys = Vec::new();
+
+    for key in &b {
+        keys.push(key);
+    }
+
+    assert_eq!(b.into_iter().collect::<Vec<_>>(), keys);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_from_iter() {
+    use fixed_map::{Map, Set};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Composite {
+        Simple,
+        Boolean(bool),
+        String(&'static str),
+    }
+
+    let a: Map<_, _> = vec![
+        (Composite::Simple, 1),
+        (Composite::Boolean(false), 2),
+        (Composite::String("foo"), 3),
+    ]
+    .into_iter()
+    .collect();
+
+    let mut b = Map::new();
+    b.extend(a.iter().map(|(k, v)| (k, *v)));
+    assert_eq!(a, b);
+
+    let keys: Set<_> = a.keys().collect();
+    assert_eq!(keys.len(), 3);
+    assert!(keys.contains(Composite::String("foo")));
+    assert!(!keys.contains(Composite::String("bar")));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_retain() {
+    use fixed_map::{Map, Set};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Part {
+        One,
+        Two,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Composite {
+        Simple,
+        Option(Option<Part>),
+        Singleton(()),
+        Number(u32),
+    }
+
+    let mut a = Map::new();
+    a.insert(Composite::Simple, 1);
+    a.insert(Composite::Option(Some(Part::One)), 2);
+    a.insert(Composite::Option(Some(Part::Two)), 3);
+    a.insert(Composite::Option(None), 4);
+    a.insert(Composite::Singleton(()), 5);
+    a.insert(Composite::Number(6), 6);
+
+    a.retain(|_, v| *v % 2 == 1);
+
+    assert_eq!(
+        a.iter().collect::<Vec<_>>(),
+        vec![
+            (Composite::Simple, &1),
+            (Composite::Option(Some(Part::Two)), &3),
+            (Composite::Singleton(()), &5),
+        ]
+    );
+
+    let mut b: Set<_> = a.keys().collect();
+    b.retain(|k| matches!(k, Composite::Option(..)));
+    assert_eq!(
+        b.iter().collect::<Vec<_>>(),
+        vec![Composite::Option(Some(Part::Two))]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_len() {
+    use fixed_map::{Map, Set};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Part {
+        One,
+        Two,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Composite {
+        Simple,
+        Part(Part),
+        Option(Option<Part>),
+        Boolean(bool),
+        Number(u32),
+        Singleton(()),
+    }
+
+    let mut a = Map::new();
+    assert_eq!(a.len(), 0);
+    assert!(a.is_empty());
+
+    a.insert(Composite::Simple, 1);
+    a.insert(Composite::Part(Part::One), 2);
+    a.insert(Composite::Option(None), 3);
+    a.insert(Composite::Option(Some(Part::Two)), 4);
+    a.insert(Composite::Boolean(true), 5);
+    a.insert(Composite::Number(1), 6);
+    a.insert(Composite::Number(2), 7);
+    a.insert(Composite::Singleton(()), 8);
+    assert_eq!(a.len(), 8);
+    assert!(!a.is_empty());
+
+    a.remove(Composite::Number(1));
+    a.remove(Composite::Option(None));
+    assert_eq!(a.len(), 6);
+
+    let b: Set<_> = a.keys().collect();
+    assert_eq!(b.len(), 6);
+    assert!(!b.is_empty());
+}
@@ -0,0 +1,88 @@
+use fixed_map::{key::Enumerable, Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Key {
+    Simple,
+    Composite(Part),
+    Boolean(bool),
+    Singleton(()),
+    Option(Option<Part>),
+    Nested(Option<Option<bool>>),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Hashed {
+    First,
+    Number(u32),
+}
+
+#[test]
+fn test_count() {
+    assert_eq!(<()>::COUNT, 1);
+    assert_eq!(bool::COUNT, 2);
+    assert_eq!(Option::<bool>::COUNT, 3);
+    assert_eq!(Part::COUNT, 2);
+    assert_eq!(Key::COUNT, 1 + 2 + 2 + 1 + 3 + 4);
+}
+
+#[test]
+fn test_ordinal_round_trip() {
+    for (n, key) in Key::all().enumerate() {
+        assert_eq!(key.ordinal(), n);
+        assert_eq!(Key::from_ordinal(n), Some(key));
+    }
+
+    assert_eq!(Key::all().len(), Key::COUNT);
+    assert_eq!(Key::from_ordinal(Key::COUNT), None);
+}
+
+#[test]
+fn test_all_rev() {
+    let mut forward = Key::all().collect::<Vec<_>>();
+    forward.reverse();
+    assert_eq!(Key::all().rev().collect::<Vec<_>>(), forward);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn test_largest_count() {
+    type Largest = [u8; 7];
+
+    assert_eq!(Largest::COUNT, 1 << 56);
+    assert_eq!(<(u8, u8, u8, u8, u8, u8, u8)>::COUNT, 1 << 56);
+    assert_eq!(Largest::all().len(), Largest::COUNT);
+    assert_eq!([255u8; 7].ordinal(), Largest::COUNT - 1);
+    assert_eq!(Largest::all().next_back(), Some([255; 7]));
+    assert_eq!(Largest::from_ordinal(Largest::COUNT), None);
+}
+
+#[test]
+fn test_matches_map_layout() {
+    let mut map = Map::new();
+
+    for key in Key::all().rev() {
+        map.insert(key, key.ordinal());
+    }
+
+    assert_eq!(
+        map.keys().collect::<Vec<_>>(),
+        Key::all().collect::<Vec<_>>()
+    );
+    assert!(map.iter().enumerate().all(|(n, (_, v))| *v == n));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_non_enumerable_payload() {
+    let mut map = Map::new();
+    map.insert(Hashed::Number(1), 1);
+    map.insert(Hashed::First, 2);
+    assert_eq!(map.get(Hashed::Number(1)), Some(&1));
+}
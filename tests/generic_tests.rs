@@ -0,0 +1,145 @@
+use fixed_map::key::Enumerable;
+#[cfg(feature = "alloc")]
+use fixed_map::Set;
+use fixed_map::{Key, Map, TotalMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Slot<T> {
+    Empty,
+    Full(T),
+    Pair(T, bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Choice<T> {
+    Default,
+    Custom(T),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Name<'a> {
+    Anonymous,
+    Named(&'a str),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Key)]
+enum Bounded<T = bool>
+where
+    T: Clone,
+{
+    Unit,
+    Value(T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+struct Cell<T> {
+    layer: T,
+    top: bool,
+}
+
+#[test]
+fn test_generic_enum() {
+    let mut map = Map::new();
+    map.insert(Slot::Full(Side::Right), 1);
+    map.insert(Slot::Empty, 2);
+    map.insert(Slot::Pair(Side::Left, true), 3);
+
+    assert_eq!(map.get(Slot::Full(Side::Right)), Some(&1));
+    assert_eq!(map.get(Slot::Full(Side::Left)), None);
+    assert_eq!(map.get(Slot::Pair(Side::Left, true)), Some(&3));
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![
+            (Slot::Empty, &2),
+            (Slot::Full(Side::Right), &1),
+            (Slot::Pair(Side::Left, true), &3),
+        ]
+    );
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut map = Map::new();
+        map.insert(Slot::Full(42u32), 1);
+        assert_eq!(map.get(Slot::Full(42)), Some(&1));
+        assert_eq!(map.get(Slot::Full(43)), None);
+
+        let mut set = Set::new();
+        set.insert(Slot::Pair(String::from("foo"), false));
+        assert!(set.contains(Slot::Pair(String::from("foo"), false)));
+        assert!(!set.contains(Slot::Pair(String::from("foo"), true)));
+    }
+}
+
+#[test]
+fn test_generic_enumerable() {
+    assert_eq!(Slot::<Side>::COUNT, 7);
+    assert_eq!(Slot::Full(Side::Right).ordinal(), 2);
+    assert_eq!(Slot::from_ordinal(6), Some(Slot::Pair(Side::Right, false)));
+
+    let mut map = TotalMap::from_fn(|_| 0);
+    *map.get_mut(Choice::Custom(Side::Left)) += 1;
+    assert_eq!(map.get(Choice::Custom(Side::Left)), &1);
+    assert_eq!(map.get(Choice::Default), &0);
+    assert_eq!(map.iter().count(), 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_lifetime() {
+    let mut map = Map::new();
+    map.insert(Name::Named("foo"), 1);
+    map.insert(Name::Anonymous, 2);
+
+    assert_eq!(map.get(Name::Named("foo")), Some(&1));
+    assert_eq!(map.get(Name::Named("bar")), None);
+    assert_eq!(map.get(Name::Anonymous), Some(&2));
+}
+
+#[test]
+fn test_where_clause_and_default() {
+    let mut map: Map<Bounded, u32> = Map::new();
+    map.insert(Bounded::Value(true), 1);
+    assert_eq!(map.get(Bounded::Value(true)), Some(&1));
+    assert_eq!(map.get(Bounded::Unit), None);
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut map = Map::new();
+        map.insert(Bounded::Value(String::from("foo")), 1);
+        assert_eq!(map.remove(Bounded::Value(String::from("foo"))), Some(1));
+    }
+}
+
+#[test]
+fn test_generic_struct() {
+    let mut map = Map::new();
+    map.insert(
+        Cell {
+            layer: Side::Left,
+            top: true,
+        },
+        1,
+    );
+
+    assert_eq!(
+        map.get(Cell {
+            layer: Side::Left,
+            top: true
+        }),
+        Some(&1)
+    );
+    assert_eq!(
+        map.get(Cell {
+            layer: Side::Right,
+            top: true
+        }),
+        None
+    );
+}
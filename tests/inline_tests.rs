@@ -0,0 +1,335 @@
+use fixed_map::map::{Entry, VacantEntry};
+use fixed_map::storage::{InlineMapStorage, Storage};
+use fixed_map::{Key, Map, Set};
+
+const CAPACITY: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Key {
+    Unit,
+    #[key(inline = 4)]
+    Number(u32),
+    #[key(inline = CAPACITY)]
+    Pair(u8, bool),
+    #[key(inline = 1 + 1)]
+    Name(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Slot<T> {
+    Empty,
+    #[key(inline = 2)]
+    Full(T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Never {
+    #[key(inline = 0)]
+    Number(u32),
+}
+
+#[test]
+fn test_insert_until_full() {
+    let mut map = Map::new();
+
+    for n in 0..4 {
+        assert_eq!(map.try_insert(Key::Number(n), n), Ok(None));
+    }
+
+    let error = map.try_insert(Key::Number(4), 4).unwrap_err();
+    assert_eq!(error.key(), &Key::Number(4));
+    assert_eq!(error.value(), &4);
+    assert_eq!(error.to_string(), "no capacity left for key Number(4)");
+    assert_eq!(error.into_inner(), (Key::Number(4), 4));
+
+    // Other variants and existing keys are unaffected by a full variant.
+    assert_eq!(map.try_insert(Key::Number(2), 20), Ok(Some(2)));
+    assert_eq!(map.try_insert(Key::Unit, 5), Ok(None));
+    assert_eq!(map.try_insert(Key::Pair(1, true), 6), Ok(None));
+    assert_eq!(map.try_insert(Key::Name("foo"), 7), Ok(None));
+    assert_eq!(map.len(), 7);
+
+    for n in 0..4 {
+        let expected = if n == 2 { 20 } else { n };
+        assert_eq!(map.get(Key::Number(n)), Some(&expected));
+    }
+
+    assert_eq!(map.get(Key::Number(4)), None);
+    assert_eq!(map.remove(Key::Number(0)), Some(0));
+    assert_eq!(map.try_insert(Key::Number(4), 4), Ok(None));
+    assert_eq!(map.get(Key::Number(4)), Some(&4));
+}
+
+#[test]
+#[should_panic(expected = "no capacity left in inline storage of 2 slots")]
+fn test_insert_panics_when_full() {
+    let mut map = Map::new();
+    map.insert(Key::Name("foo"), 1);
+    map.insert(Key::Name("bar"), 2);
+    map.insert(Key::Name("baz"), 3);
+}
+
+#[test]
+#[should_panic(expected = "no capacity left in inline storage of 2 slots")]
+fn test_vacant_entry_panics_when_full() {
+    let mut map = Map::new();
+    map.insert(Key::Name("foo"), 1);
+    map.insert(Key::Name("bar"), 2);
+
+    match map.entry(Key::Name("baz")) {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), Key::Name("baz"));
+            entry.insert(3);
+        }
+        Entry::Occupied(..) => panic!("expected a vacant entry"),
+    }
+}
+
+fn try_insert_vacant<'a, K, V>(entry: Entry<'a, K::Storage, K, V>, value: V) -> Result<V, (K, V)>
+where
+    K: fixed_map::key::Key<K, V>,
+    V: 'a + Copy,
+{
+    match entry {
+        Entry::Vacant(entry) => entry
+            .try_insert(value)
+            .map(|value| *value)
+            .map_err(|error| error.into_inner()),
+        Entry::Occupied(..) => panic!("expected a vacant entry"),
+    }
+}
+
+#[test]
+fn test_vacant_entry_try_insert() {
+    let mut map = Map::new();
+    assert_eq!(try_insert_vacant(map.entry(Key::Name("foo")), 1), Ok(1));
+    assert_eq!(try_insert_vacant(map.entry(Key::Name("bar")), 2), Ok(2));
+    assert_eq!(
+        try_insert_vacant(map.entry(Key::Name("baz")), 3),
+        Err((Key::Name("baz"), 3))
+    );
+    assert_eq!(try_insert_vacant(map.entry(Key::Unit), 4), Ok(4));
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(Key::Name("baz")), None);
+
+    assert_eq!(map.entry(Key::Name("foo")).or_try_insert(5), Ok(&mut 1));
+    let error = map.entry(Key::Name("baz")).or_try_insert(6).unwrap_err();
+    assert_eq!(error.into_inner(), (Key::Name("baz"), 6));
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn test_vacant_entry_try_insert_composite() {
+    let mut map = Map::new();
+    map.insert(Some(Key::Name("foo")), 1);
+    map.insert(Some(Key::Name("bar")), 2);
+
+    assert_eq!(
+        try_insert_vacant(map.entry(Some(Key::Name("baz"))), 3),
+        Err((Some(Key::Name("baz")), 3))
+    );
+    assert_eq!(try_insert_vacant(map.entry(None), 4), Ok(4));
+    assert_eq!(map.len(), 3);
+
+    let mut map = Map::new();
+    map.insert(Err::<Key, Key>(Key::Name("foo")), 1);
+    map.insert(Err(Key::Name("bar")), 2);
+
+    assert_eq!(
+        try_insert_vacant(map.entry(Err(Key::Name("baz"))), 3),
+        Err((Err(Key::Name("baz")), 3))
+    );
+    assert_eq!(try_insert_vacant(map.entry(Ok(Key::Name("baz"))), 4), Ok(4));
+    assert_eq!(map.len(), 3);
+
+    // The storage of the head of the key is full.
+    let mut map = Map::new();
+    map.insert((Key::Name("foo"), true), 1);
+    map.insert((Key::Name("bar"), true), 2);
+
+    assert_eq!(
+        try_insert_vacant(map.entry((Key::Name("baz"), true)), 3),
+        Err(((Key::Name("baz"), true), 3))
+    );
+    assert_eq!(
+        try_insert_vacant(map.entry((Key::Name("foo"), false)), 4),
+        Ok(4)
+    );
+    assert_eq!(try_insert_vacant(map.entry((Key::Unit, false)), 5), Ok(5));
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.get((Key::Unit, false)), Some(&5));
+
+    // The storage of the tail of the key is full.
+    let mut map = Map::new();
+    map.insert((true, Key::Name("foo")), 1);
+    map.insert((true, Key::Name("bar")), 2);
+
+    assert_eq!(
+        try_insert_vacant(map.entry((true, Key::Name("baz"))), 3),
+        Err(((true, Key::Name("baz")), 3))
+    );
+    assert_eq!(
+        try_insert_vacant(map.entry((false, Key::Name("baz"))), 4),
+        Ok(4)
+    );
+    assert_eq!(map.len(), 3);
+
+    // A key whose tail can't be stored leaves no storage behind for its head.
+    let mut map = Map::new();
+    assert_eq!(
+        try_insert_vacant(map.entry((true, Never::Number(1))), 1),
+        Err(((true, Never::Number(1)), 1))
+    );
+    assert!(map.is_empty());
+    assert_eq!(map.iter().next(), None);
+}
+
+#[test]
+fn test_entry_and_retain() {
+    let mut map = Map::new();
+
+    for n in 0..3u8 {
+        *map.entry(Key::Pair(n, false)).or_insert(0) += 1;
+    }
+
+    *map.entry(Key::Pair(1, false)).or_insert(0) += 1;
+    assert_eq!(map.get(Key::Pair(1, false)), Some(&2));
+
+    map.insert(Key::Unit, 10);
+    map.retain(|key, value| key == Key::Unit || *value > 1);
+
+    let mut entries = map.iter().map(|(k, v)| (k, *v)).collect::<Vec<_>>();
+    entries.sort_by_key(|&(_, v)| v);
+    assert_eq!(entries, vec![(Key::Pair(1, false), 2), (Key::Unit, 10)]);
+
+    // Room was freed up by the entries which weren't retained.
+    assert_eq!(map.try_insert(Key::Pair(2, true), 3), Ok(None));
+    assert_eq!(map.try_insert(Key::Pair(3, true), 4), Ok(None));
+    assert!(map.try_insert(Key::Pair(4, true), 5).is_err());
+}
+
+#[test]
+fn test_eq_ignores_insertion_order() {
+    let mut a = Map::new();
+    let mut b = Map::new();
+
+    for n in 0..4 {
+        a.insert(Key::Number(n), n);
+        b.insert(Key::Number(3 - n), 3 - n);
+    }
+
+    assert_eq!(a, b);
+    b.insert(Key::Number(0), 10);
+    assert_ne!(a, b);
+    assert_eq!(a.clone(), a);
+}
+
+#[test]
+fn test_set_and_generics() {
+    let mut set = Set::new();
+    assert!(set.insert(Slot::Full('a')));
+    assert!(set.insert(Slot::Full('b')));
+    assert!(!set.insert(Slot::Full('a')));
+    assert!(set.insert(Slot::Empty));
+
+    assert!(set.contains(Slot::Full('b')));
+    assert!(set.remove(Slot::Full('a')));
+    assert!(!set.contains(Slot::Full('a')));
+    assert!(set.insert(Slot::Full('c')));
+    assert_eq!(set.len(), 3);
+
+    let error = set.try_insert(Slot::Full('d')).unwrap_err();
+    assert_eq!(error.into_inner(), (Slot::Full('d'), ()));
+    assert_eq!(set.try_insert(Slot::Full('b')), Ok(false));
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn test_try_insert_option_and_result() {
+    let mut map = Map::new();
+    map.insert(Some(Key::Name("foo")), 1);
+    map.insert(Some(Key::Name("bar")), 2);
+
+    let error = map.try_insert(Some(Key::Name("baz")), 3).unwrap_err();
+    assert_eq!(error.into_inner(), (Some(Key::Name("baz")), 3));
+    assert_eq!(map.try_insert(Some(Key::Name("foo")), 4), Ok(Some(1)));
+    assert_eq!(map.try_insert(None, 5), Ok(None));
+    assert_eq!(map.len(), 3);
+
+    let mut map = Map::new();
+    map.insert(Ok::<Key, Key>(Key::Name("foo")), 1);
+    map.insert(Ok(Key::Name("bar")), 2);
+
+    let error = map.try_insert(Ok(Key::Name("baz")), 3).unwrap_err();
+    assert_eq!(error.into_inner(), (Ok(Key::Name("baz")), 3));
+    assert_eq!(map.try_insert(Err(Key::Name("baz")), 4), Ok(None));
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn test_try_insert_tuple() {
+    // The storage of the head of the key is full.
+    let mut map = Map::new();
+    map.insert((Key::Name("foo"), true), 1);
+    map.insert((Key::Name("bar"), true), 2);
+
+    let error = map.try_insert((Key::Name("baz"), true), 3).unwrap_err();
+    assert_eq!(error.into_inner(), ((Key::Name("baz"), true), 3));
+    assert_eq!(map.try_insert((Key::Name("foo"), false), 4), Ok(None));
+    assert_eq!(map.try_insert((Key::Name("foo"), false), 5), Ok(Some(4)));
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get((Key::Name("baz"), true)), None);
+
+    // The storage of the tail of the key is full.
+    let mut map = Map::new();
+    map.insert((true, Key::Name("foo")), 1);
+    map.insert((true, Key::Name("bar")), 2);
+
+    let error = map.try_insert((true, Key::Name("baz")), 3).unwrap_err();
+    assert_eq!(error.into_inner(), ((true, Key::Name("baz")), 3));
+    assert_eq!(map.try_insert((false, Key::Name("baz")), 4), Ok(None));
+    assert_eq!(map.len(), 3);
+
+    // A key whose tail can't be stored leaves no storage behind for its head.
+    let mut map = Map::new();
+    assert!(map.try_insert((true, Never::Number(1)), 1).is_err());
+    assert!(map.is_empty());
+    assert_eq!(map.iter().next(), None);
+}
+
+#[test]
+fn test_zero_capacity() {
+    let mut map = Map::new();
+    assert!(map.try_insert(Never::Number(1), 1).is_err());
+    assert_eq!(map.get(Never::Number(1)), None);
+    assert_eq!(map.remove(Never::Number(1)), None);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_remove_keeps_probed_keys() {
+    // Remove the keys of a full storage in a different order each time,
+    // checking that every remaining key is still reachable after each removal.
+    for start in 0..16 {
+        let mut storage = InlineMapStorage::<u32, u32, 16>::default();
+
+        for n in 0..16 {
+            assert_eq!(storage.insert(n * 7, n), None);
+        }
+
+        for removed in 0..16 {
+            let n = (start + removed * 5) % 16;
+            assert_eq!(storage.remove(&(n * 7)), Some(n));
+            assert_eq!(storage.remove(&(n * 7)), None);
+            assert_eq!(storage.len(), 15 - removed as usize);
+
+            for other in (removed + 1)..16 {
+                let n = (start + other * 5) % 16;
+                assert_eq!(storage.get(&(n * 7)), Some(&n));
+            }
+        }
+
+        assert!(storage.is_empty());
+        assert_eq!(storage.into_iter().next(), None);
+    }
+}
@@ -0,0 +1,62 @@
+//! Keys which are stored inline, used from a crate without `std`.
+
+#![no_std]
+
+use fixed_map::{Key, Map, Set, TotalMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+#[key(bitset)]
+enum Flag {
+    First,
+    Second,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Key {
+    Unit,
+    Side(Side),
+    Pair(Side, Option<bool>),
+    #[key(inline = 2)]
+    Number(u32),
+}
+
+#[test]
+fn test_map() {
+    let mut map = Map::new();
+    map.insert(Key::Side(Side::Left), 1);
+    map.insert(Key::Pair(Side::Right, None), 2);
+
+    assert_eq!(map.get(Key::Side(Side::Left)), Some(&1));
+    assert_eq!(map.get(Key::Pair(Side::Right, None)), Some(&2));
+    assert_eq!(map.get(Key::Unit), None);
+    assert!(map.iter().map(|(_, v)| *v).eq([1, 2].iter().copied()));
+
+    map.insert(Key::Number(1), 3);
+    map.insert(Key::Number(2), 4);
+    assert_eq!(map.get(Key::Number(2)), Some(&4));
+    assert!(map.try_insert(Key::Number(3), 5).is_err());
+}
+
+#[test]
+fn test_set_and_total_map() {
+    let mut set = Set::new();
+    set.insert(Flag::Second);
+    assert!(set.contains(Flag::Second));
+    assert!(!set.contains(Flag::First));
+
+    let mut bytes = Set::new();
+    bytes.insert(b'a');
+    bytes.insert(-1i8 as u8);
+    assert!(bytes.contains(b'a'));
+    assert_eq!(bytes.len(), 2);
+
+    let mut map = TotalMap::from_fn(|side| side == Side::Right);
+    *map.get_mut(Side::Left) = true;
+    assert!(map.iter().all(|(_, &v)| v));
+}
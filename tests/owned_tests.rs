@@ -0,0 +1,126 @@
+#![cfg(feature = "alloc")]
+
+use fixed_map::map::{Entry, OccupiedEntry, VacantEntry};
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, PartialEq, Eq, Key)]
+enum Ident {
+    SelfValue,
+    Super,
+    Custom(String),
+    Scoped(bool, String),
+}
+
+fn custom(name: &str) -> Ident {
+    Ident::Custom(String::from(name))
+}
+
+#[test]
+fn test_string_variant() {
+    let foo = custom("foo");
+    let bar = custom("bar");
+    let mut map = Map::new();
+
+    assert_eq!(map.insert(Ident::SelfValue, 1), None);
+    assert_eq!(map.insert(foo.clone(), 2), None);
+    assert_eq!(map.insert(bar.clone(), 3), None);
+    assert_eq!(map.insert(foo.clone(), 4), Some(2));
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(&Ident::SelfValue), Some(&1));
+    assert_eq!(map.get(&Ident::Super), None);
+    assert_eq!(map.get(&foo), Some(&4));
+    assert_eq!(map.get(custom("baz")), None);
+
+    if let Some(value) = map.get_mut(&bar) {
+        *value += 10;
+    }
+
+    assert_eq!(map.get(&bar), Some(&13));
+    assert_eq!(map.remove(&foo), Some(4));
+    assert_eq!(map.remove(&foo), None);
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_multi_field_variant() {
+    let a = Ident::Scoped(true, String::from("foo"));
+    let b = Ident::Scoped(false, String::from("foo"));
+    let mut map = Map::new();
+
+    map.insert(a.clone(), 1);
+    map.insert(b.clone(), 2);
+
+    assert_eq!(map.get(&a), Some(&1));
+    assert_eq!(map.get(&b), Some(&2));
+    assert_eq!(map.get(Ident::Scoped(true, String::from("bar"))), None);
+    assert_eq!(map.remove(&b), Some(2));
+    assert_eq!(map.remove(b), None);
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_entry_and_retain() {
+    let mut map = Map::new();
+
+    match map.entry(custom("foo")) {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), custom("foo"));
+            entry.insert(1);
+        }
+        Entry::Occupied(..) => panic!("expected a vacant entry"),
+    }
+
+    match map.entry(custom("foo")) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.key(), custom("foo"));
+            assert_eq!(entry.insert(2), 1);
+        }
+        Entry::Vacant(..) => panic!("expected an occupied entry"),
+    }
+
+    map.insert(Ident::Super, 3);
+    map.insert(custom("bar"), 4);
+    map.retain(|key, _| key != custom("bar"));
+
+    let mut entries = map.into_iter().collect::<Vec<_>>();
+    entries.sort_by_key(|&(_, value)| value);
+    assert_eq!(entries, vec![(custom("foo"), 2), (Ident::Super, 3)]);
+}
+
+#[test]
+fn test_set() {
+    let foo = custom("foo");
+    let mut set = Set::new();
+
+    assert!(set.insert(foo.clone()));
+    assert!(!set.insert(foo.clone()));
+    assert!(set.insert(Ident::SelfValue));
+
+    assert!(set.contains(&foo));
+    assert!(!set.contains(custom("bar")));
+    assert!(set.contains(&Ident::SelfValue));
+
+    assert!(set.remove(&foo));
+    assert!(!set.contains(&foo));
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![Ident::SelfValue]);
+}
+
+#[test]
+fn test_owned_primitives() {
+    let foo = String::from("foo");
+    let mut map = Map::new();
+    map.insert(foo.clone(), 1);
+    assert_eq!(map.get(&foo), Some(&1));
+
+    let mut map = Map::new();
+    map.insert(Box::<str>::from("foo"), 1);
+    assert_eq!(map.get(Box::<str>::from("foo")), Some(&1));
+
+    let key = (foo.clone(), false);
+    let mut map = Map::new();
+    map.insert((foo, true), 1);
+    map.insert(key.clone(), 2);
+    assert_eq!(map.get(&key), Some(&2));
+    assert_eq!(map.get((String::from("bar"), false)), None);
+}
@@ -0,0 +1,164 @@
+use fixed_map::key::{self, Enumerable};
+use fixed_map::map::{Entry, OccupiedEntry};
+#[cfg(feature = "alloc")]
+use fixed_map::Key;
+use fixed_map::{Map, Set};
+use std::cmp::Ordering;
+use std::fmt;
+#[cfg(feature = "alloc")]
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+
+/// Check that the given keys round-trip through both a `Map` and a `Set`.
+fn round_trip<K>(a: K, b: K, missing: K)
+where
+    K: key::Key<K, usize> + key::Key<K, ()> + Copy + fmt::Debug + PartialEq,
+{
+    let mut map = Map::new();
+    assert_eq!(map.insert(a, 1), None);
+    assert_eq!(map.insert(b, 2), None);
+    assert_eq!(map.insert(a, 3), Some(1));
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(a), Some(&3));
+    assert_eq!(map.get(b), Some(&2));
+    assert_eq!(map.get(missing), None);
+
+    let mut keys = map.keys().collect::<Vec<_>>();
+    assert_eq!(keys.len(), 2);
+    assert!(keys.contains(&a) && keys.contains(&b));
+
+    assert_eq!(map.remove(a), Some(3));
+    assert_eq!(map.remove(a), None);
+    keys = map.keys().collect();
+    assert_eq!(keys, vec![b]);
+
+    let mut set = Set::new();
+    assert!(set.insert(a));
+    assert!(!set.insert(a));
+    assert!(set.insert(b));
+
+    assert!(set.contains(a));
+    assert!(set.contains(b));
+    assert!(!set.contains(missing));
+    assert!(set.remove(b));
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![a]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_small_integers() {
+    round_trip(1u16, u16::MAX, 2);
+    round_trip(-1i16, i16::MIN, 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_non_zero() {
+    macro_rules! non_zero {
+        ($($ty:ident),*) => {
+            $(
+                round_trip(
+                    $ty::new(1).unwrap(),
+                    $ty::new(2).unwrap(),
+                    $ty::new(3).unwrap(),
+                );
+            )*
+        };
+    }
+
+    non_zero!(
+        NonZeroU8,
+        NonZeroU16,
+        NonZeroU32,
+        NonZeroU64,
+        NonZeroU128,
+        NonZeroUsize,
+        NonZeroI8,
+        NonZeroI16,
+        NonZeroI32,
+        NonZeroI64,
+        NonZeroI128,
+        NonZeroIsize
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_wrapping() {
+    round_trip(Wrapping(1u32), Wrapping(u32::MAX), Wrapping(0));
+    round_trip(Wrapping(-1i8), Wrapping(i8::MAX), Wrapping(0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_byte_strings() {
+    round_trip(&b"foo"[..], &b""[..], &b"bar"[..]);
+}
+
+#[test]
+fn test_ordering() {
+    round_trip(Ordering::Less, Ordering::Greater, Ordering::Equal);
+
+    let mut map = Map::new();
+
+    let keys = [Ordering::Greater, Ordering::Less, Ordering::Equal];
+
+    for (n, &key) in keys.iter().enumerate() {
+        map.insert(key, n);
+    }
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![
+            (Ordering::Less, &1),
+            (Ordering::Equal, &2),
+            (Ordering::Greater, &0)
+        ]
+    );
+
+    match map.entry(Ordering::Equal) {
+        Entry::Occupied(entry) => assert_eq!(entry.remove(), 2),
+        Entry::Vacant(..) => panic!("expected an occupied entry"),
+    }
+
+    map.retain(|key, _| key != Ordering::Less);
+    assert_eq!(
+        map.into_iter().collect::<Vec<_>>(),
+        vec![(Ordering::Greater, 0)]
+    );
+
+    assert_eq!(Ordering::COUNT, 3);
+    assert_eq!(Ordering::Greater.ordinal(), 2);
+    assert_eq!(
+        Ordering::all().collect::<Vec<_>>(),
+        vec![Ordering::Less, Ordering::Equal, Ordering::Greater]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Compare {
+    Result(Ordering),
+    Port(NonZeroU16),
+    Bytes(&'static [u8]),
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_derive() {
+    let port = NonZeroU16::new(8080).unwrap();
+
+    let mut map = Map::new();
+    map.insert(Compare::Result(Ordering::Less), 1);
+    map.insert(Compare::Port(port), 2);
+    map.insert(Compare::Bytes(b"GET"), 3);
+
+    assert_eq!(map.get(Compare::Result(Ordering::Less)), Some(&1));
+    assert_eq!(map.get(Compare::Result(Ordering::Equal)), None);
+    assert_eq!(map.get(Compare::Port(port)), Some(&2));
+    assert_eq!(map.get(Compare::Bytes(b"GET")), Some(&3));
+    assert_eq!(map.get(Compare::Bytes(b"PUT")), None);
+}
@@ -0,0 +1,130 @@
+use fixed_map::key::Enumerable;
+#[cfg(feature = "alloc")]
+use fixed_map::map::{Entry, OccupiedEntry, VacantEntry};
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Phase {
+    Parse,
+    Check,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum ErrorKind {
+    Syntax,
+    Io,
+}
+
+#[test]
+fn test_result_keys() {
+    let mut map = Map::new();
+
+    assert_eq!(map.insert(Ok(Phase::Check), 1), None);
+    assert_eq!(map.insert(Err(ErrorKind::Io), 2), None);
+    assert_eq!(map.insert(Ok(Phase::Check), 3), Some(1));
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(Ok(Phase::Check)), Some(&3));
+    assert_eq!(map.get(Ok(Phase::Parse)), None);
+    assert_eq!(map.get(Err(ErrorKind::Io)), Some(&2));
+    assert_eq!(map.get(Err(ErrorKind::Syntax)), None);
+
+    assert_eq!(map.remove(Err(ErrorKind::Io)), Some(2));
+    assert_eq!(map.remove(Err(ErrorKind::Io)), None);
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_iter_order() {
+    let mut map = Map::new();
+    map.insert(Err(ErrorKind::Syntax), 1);
+    map.insert(Ok(Phase::Check), 2);
+    map.insert(Ok(Phase::Parse), 3);
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![
+            (Ok(Phase::Parse), &3),
+            (Ok(Phase::Check), &2),
+            (Err(ErrorKind::Syntax), &1),
+        ]
+    );
+
+    for (_, value) in map.iter_mut() {
+        *value *= 10;
+    }
+
+    map.retain(|key, _| key != Ok(Phase::Check));
+
+    assert_eq!(
+        map.into_iter().collect::<Vec<_>>(),
+        vec![(Ok(Phase::Parse), 30), (Err(ErrorKind::Syntax), 10)]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_entry() {
+    let mut map = Map::<Result<Phase, u32>, u32>::new();
+
+    match map.entry(Err(404)) {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), Err(404));
+            entry.insert(1);
+        }
+        Entry::Occupied(..) => panic!("expected a vacant entry"),
+    }
+
+    match map.entry(Err(404)) {
+        Entry::Occupied(entry) => {
+            assert_eq!(entry.key(), Err(404));
+            assert_eq!(entry.remove(), 1);
+        }
+        Entry::Vacant(..) => panic!("expected an occupied entry"),
+    }
+
+    *map.entry(Ok(Phase::Parse)).or_insert(0) += 1;
+    *map.entry(Ok(Phase::Parse)).or_insert(0) += 1;
+    assert_eq!(map.get(Ok(Phase::Parse)), Some(&2));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_clone_and_eq() {
+    let mut a = Map::new();
+    a.insert(Ok::<_, ErrorKind>(Phase::Parse), 1);
+    a.insert(Err(ErrorKind::Syntax), 2);
+
+    let mut b = a.clone();
+    assert_eq!(a, b);
+
+    b.insert(Err(ErrorKind::Syntax), 3);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_set_and_enumerable() {
+    let mut set = Set::new();
+    set.insert(Ok::<bool, Phase>(true));
+    set.insert(Err(Phase::Check));
+
+    assert!(set.contains(Ok(true)));
+    assert!(!set.contains(Ok(false)));
+    assert!(set.contains(Err(Phase::Check)));
+
+    type Outcome = Result<Phase, ErrorKind>;
+
+    assert_eq!(Outcome::COUNT, 4);
+    assert_eq!(Err::<Phase, _>(ErrorKind::Io).ordinal(), 3);
+    assert_eq!(Outcome::from_ordinal(2), Some(Err(ErrorKind::Syntax)));
+    assert_eq!(Outcome::from_ordinal(4), None);
+    assert_eq!(
+        Outcome::all().collect::<Vec<_>>(),
+        vec![
+            Ok(Phase::Parse),
+            Ok(Phase::Check),
+            Err(ErrorKind::Syntax),
+            Err(ErrorKind::Io),
+        ]
+    );
+}
@@ -0,0 +1,167 @@
+#![cfg(feature = "serde")]
+
+use fixed_map::{Key, Map, Set};
+use serde::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Key)]
+enum Key {
+    Simple,
+    Composite(Part),
+    Option(Option<Part>),
+    Boolean(bool),
+    Number(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Key)]
+enum Bounded {
+    Simple,
+    #[key(inline = 1)]
+    Number(u32),
+}
+
+#[test]
+fn test_map() {
+    let mut map = Map::new();
+    map.insert(Key::Simple, 1u32);
+    map.insert(Key::Composite(Part::Two), 2);
+    map.insert(Key::Option(None), 3);
+    map.insert(Key::Boolean(true), 4);
+
+    assert_tokens(
+        &map,
+        &[
+            Token::Map { len: Some(4) },
+            Token::UnitVariant {
+                name: "Key",
+                variant: "Simple",
+            },
+            Token::U32(1),
+            Token::NewtypeVariant {
+                name: "Key",
+                variant: "Composite",
+            },
+            Token::UnitVariant {
+                name: "Part",
+                variant: "Two",
+            },
+            Token::U32(2),
+            Token::NewtypeVariant {
+                name: "Key",
+                variant: "Option",
+            },
+            Token::None,
+            Token::U32(3),
+            Token::NewtypeVariant {
+                name: "Key",
+                variant: "Boolean",
+            },
+            Token::Bool(true),
+            Token::U32(4),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_set() {
+    let mut set = Set::new();
+    set.insert(Key::Composite(Part::One));
+    set.insert(Key::Option(Some(Part::Two)));
+    set.insert(Key::Boolean(false));
+
+    assert_tokens(
+        &set,
+        &[
+            Token::Seq { len: Some(3) },
+            Token::NewtypeVariant {
+                name: "Key",
+                variant: "Composite",
+            },
+            Token::UnitVariant {
+                name: "Part",
+                variant: "One",
+            },
+            Token::NewtypeVariant {
+                name: "Key",
+                variant: "Option",
+            },
+            Token::Some,
+            Token::UnitVariant {
+                name: "Part",
+                variant: "Two",
+            },
+            Token::NewtypeVariant {
+                name: "Key",
+                variant: "Boolean",
+            },
+            Token::Bool(false),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_hashed_round_trip() {
+    let mut map = Map::new();
+    map.insert(Key::Number(7), "seven".to_string());
+
+    assert_tokens(
+        &map,
+        &[
+            Token::Map { len: Some(1) },
+            Token::NewtypeVariant {
+                name: "Key",
+                variant: "Number",
+            },
+            Token::U32(7),
+            Token::String("seven"),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_empty() {
+    assert_tokens(
+        &Map::<Part, u32>::new(),
+        &[Token::Map { len: Some(0) }, Token::MapEnd],
+    );
+    assert_tokens(
+        &Set::<Part>::new(),
+        &[Token::Seq { len: Some(0) }, Token::SeqEnd],
+    );
+}
+
+#[test]
+fn test_no_capacity_left() {
+    let number = |n| {
+        [
+            Token::NewtypeVariant {
+                name: "Bounded",
+                variant: "Number",
+            },
+            Token::U32(n),
+        ]
+    };
+
+    let mut tokens = vec![Token::Map { len: Some(2) }];
+    tokens.extend(number(1));
+    tokens.push(Token::U32(1));
+    tokens.extend(number(2));
+    tokens.push(Token::U32(2));
+    tokens.push(Token::MapEnd);
+    assert_de_tokens_error::<Map<Bounded, u32>>(&tokens, "no capacity left for key");
+
+    let mut tokens = vec![Token::Seq { len: Some(2) }];
+    tokens.extend(number(1));
+    tokens.extend(number(2));
+    tokens.push(Token::SeqEnd);
+    assert_de_tokens_error::<Set<Bounded>>(&tokens, "no capacity left for key");
+}
@@ -0,0 +1,177 @@
+use fixed_map::{Key, Set};
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+enum Key {
+    Simple,
+    Composite(Part),
+    Option(Option<Part>),
+    Number(u32),
+    String(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+#[key(bitset)]
+enum Bit {
+    First,
+    Second,
+    Third,
+}
+
+#[cfg(feature = "alloc")]
+fn sorted(mut keys: Vec<Key>) -> Vec<Key> {
+    keys.sort_by_key(|k| format!("{:?}", k));
+    keys
+}
+
+#[cfg(feature = "alloc")]
+fn sets() -> (Set<Key>, Set<Key>) {
+    let a = vec![
+        Key::Simple,
+        Key::Composite(Part::One),
+        Key::Option(None),
+        Key::Number(1),
+        Key::String("a"),
+    ]
+    .into_iter()
+    .collect();
+
+    let b = vec![
+        Key::Composite(Part::One),
+        Key::Composite(Part::Two),
+        Key::Option(Some(Part::Two)),
+        Key::Number(1),
+        Key::Number(2),
+        Key::String("b"),
+    ]
+    .into_iter()
+    .collect();
+
+    (a, b)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_union() {
+    let (a, b) = sets();
+
+    let expected = sorted(vec![
+        Key::Simple,
+        Key::Composite(Part::One),
+        Key::Composite(Part::Two),
+        Key::Option(None),
+        Key::Option(Some(Part::Two)),
+        Key::Number(1),
+        Key::Number(2),
+        Key::String("a"),
+        Key::String("b"),
+    ]);
+
+    assert_eq!(sorted(a.union(&b).collect()), expected);
+    assert_eq!(sorted((&a | &b).into_iter().collect()), expected);
+
+    let mut c = a.clone();
+    c.union_with(&b);
+    assert_eq!(sorted(c.iter().collect()), expected);
+
+    let mut c = a;
+    c |= &b;
+    assert_eq!(sorted(c.iter().collect()), expected);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_intersection() {
+    let (a, b) = sets();
+
+    let expected = sorted(vec![Key::Composite(Part::One), Key::Number(1)]);
+
+    assert_eq!(sorted(a.intersection(&b).collect()), expected);
+    assert_eq!(sorted((&a & &b).into_iter().collect()), expected);
+
+    let mut c = a;
+    c &= &b;
+    assert_eq!(sorted(c.iter().collect()), expected);
+    assert_eq!(c.len(), 2);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_difference() {
+    let (a, b) = sets();
+
+    let expected = sorted(vec![Key::Simple, Key::Option(None), Key::String("a")]);
+
+    assert_eq!(sorted(a.difference(&b).collect()), expected);
+    assert_eq!(sorted((&a - &b).into_iter().collect()), expected);
+
+    let mut c = a;
+    c -= &b;
+    assert_eq!(sorted(c.iter().collect()), expected);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_symmetric_difference() {
+    let (a, b) = sets();
+
+    let expected = sorted(vec![
+        Key::Simple,
+        Key::Composite(Part::Two),
+        Key::Option(None),
+        Key::Option(Some(Part::Two)),
+        Key::Number(2),
+        Key::String("a"),
+        Key::String("b"),
+    ]);
+
+    assert_eq!(sorted(a.symmetric_difference(&b).collect()), expected);
+    assert_eq!(sorted((&a ^ &b).into_iter().collect()), expected);
+
+    let mut c = a;
+    c ^= &b;
+    assert_eq!(sorted(c.iter().collect()), expected);
+    assert_eq!(c.len(), expected.len());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_relations() {
+    let (a, b) = sets();
+
+    let i = &a & &b;
+    assert!(i.is_subset(&a));
+    assert!(i.is_subset(&b));
+    assert!(a.is_superset(&i));
+    assert!(!a.is_subset(&b));
+    assert!(!a.is_disjoint(&b));
+    assert!((&a - &b).is_disjoint(&b));
+    assert!(Set::new().is_subset(&a));
+    assert!(Set::<Key>::new().is_disjoint(&Set::new()));
+}
+
+#[test]
+fn test_bitset() {
+    let a: Set<_> = vec![Bit::First, Bit::Second].into_iter().collect();
+    let b: Set<_> = vec![Bit::Second, Bit::Third].into_iter().collect();
+
+    assert_eq!(
+        (&a | &b).iter().collect::<Vec<_>>(),
+        vec![Bit::First, Bit::Second, Bit::Third]
+    );
+    assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![Bit::Second]);
+    assert_eq!((&a - &b).iter().collect::<Vec<_>>(), vec![Bit::First]);
+    assert_eq!(
+        (&a ^ &b).iter().collect::<Vec<_>>(),
+        vec![Bit::First, Bit::Third]
+    );
+    assert!((&a & &b).is_subset(&a));
+    assert!(!a.is_disjoint(&b));
+}
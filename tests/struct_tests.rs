@@ -0,0 +1,152 @@
+use fixed_map::map::Entry;
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Layer {
+    Top,
+    Bottom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+struct Cell {
+    layer: Layer,
+    side: Side,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+struct Wide(Layer, Option<Side>, bool, u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+struct Single(Side);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+struct Unit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Nested {
+    Cell(Cell),
+    Plain,
+}
+
+fn cell(layer: Layer, side: Side) -> Cell {
+    Cell { layer, side }
+}
+
+#[test]
+fn test_struct_map() {
+    let mut map = Map::new();
+
+    assert_eq!(map.insert(cell(Layer::Top, Side::Left), 1), None);
+    assert_eq!(map.insert(cell(Layer::Bottom, Side::Right), 2), None);
+    assert_eq!(map.insert(cell(Layer::Top, Side::Left), 3), Some(1));
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(cell(Layer::Top, Side::Left)), Some(&3));
+    assert_eq!(map.get(cell(Layer::Top, Side::Right)), None);
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![
+            (cell(Layer::Top, Side::Left), &3),
+            (cell(Layer::Bottom, Side::Right), &2),
+        ]
+    );
+
+    assert_eq!(map.remove(cell(Layer::Top, Side::Left)), Some(3));
+    assert_eq!(map.remove(cell(Layer::Top, Side::Left)), None);
+    assert_eq!(map.len(), 1);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_wide_struct() {
+    let mut map = Map::new();
+    let a = Wide(Layer::Top, None, true, 1);
+    let b = Wide(Layer::Top, Some(Side::Right), false, 1);
+    let c = Wide(Layer::Bottom, Some(Side::Right), true, 42);
+
+    map.insert(a, "a");
+    map.insert(b, "b");
+    map.insert(c, "c");
+
+    assert_eq!(map.get(a), Some(&"a"));
+    assert_eq!(map.get(b), Some(&"b"));
+    assert_eq!(map.get(c), Some(&"c"));
+    assert_eq!(map.get(Wide(Layer::Top, None, false, 1)), None);
+
+    let mut keys = map.keys().collect::<Vec<_>>();
+    keys.sort_by_key(|k| k.3);
+    assert_eq!(keys.len(), 3);
+
+    map.retain(|k, _| k.2);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(b), None);
+
+    let values = map.into_iter().map(|(_, v)| v).collect::<Vec<_>>();
+    assert_eq!(values.len(), 2);
+}
+
+#[test]
+fn test_small_structs() {
+    let mut map = Map::new();
+    map.insert(Single(Side::Right), 1);
+    assert_eq!(map.get(Single(Side::Right)), Some(&1));
+    assert_eq!(map.get(Single(Side::Left)), None);
+
+    let mut map = Map::new();
+    assert_eq!(map.get(Unit), None);
+    map.insert(Unit, 1);
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec![(Unit, &1)]);
+}
+
+#[test]
+fn test_struct_entry() {
+    let mut map = Map::new();
+    let key = cell(Layer::Bottom, Side::Left);
+
+    *map.entry(key).or_insert(0) += 1;
+    *map.entry(key).or_insert(0) += 1;
+    *map.entry(cell(Layer::Bottom, Side::Right)).or_default() += 5;
+
+    assert_eq!(map.get(key), Some(&2));
+    assert_eq!(map.len(), 2);
+
+    match map.entry(key) {
+        Entry::Occupied(entry) => {
+            assert_eq!(fixed_map::map::OccupiedEntry::remove(entry), 2);
+        }
+        Entry::Vacant(..) => panic!("expected occupied entry"),
+    }
+
+    assert_eq!(map.len(), 1);
+
+    let mut other = Map::new();
+    other.insert(cell(Layer::Bottom, Side::Right), 5);
+    assert_eq!(map, other);
+}
+
+#[test]
+fn test_struct_set_and_nesting() {
+    let mut set = Set::new();
+    set.insert(cell(Layer::Top, Side::Right));
+    set.insert(cell(Layer::Top, Side::Right));
+    assert_eq!(set.len(), 1);
+    assert!(set.contains(cell(Layer::Top, Side::Right)));
+
+    let mut map = Map::new();
+    map.insert(Nested::Cell(cell(Layer::Top, Side::Left)), 1);
+    map.insert(Nested::Plain, 2);
+    assert_eq!(
+        map.get(Nested::Cell(cell(Layer::Top, Side::Left))),
+        Some(&1)
+    );
+    assert_eq!(map.get(Nested::Cell(cell(Layer::Top, Side::Right))), None);
+    assert_eq!(map.len(), 2);
+}
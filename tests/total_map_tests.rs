@@ -0,0 +1,130 @@
+use fixed_map::{total_map, Key, Map, TotalMap};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Key {
+    Simple,
+    Composite(Part),
+    Singleton(()),
+    Option(Option<Part>),
+    Boolean(bool),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Hashed {
+    First,
+    Number(u32),
+}
+
+const KEYS: [Key; 9] = [
+    Key::Simple,
+    Key::Composite(Part::One),
+    Key::Composite(Part::Two),
+    Key::Singleton(()),
+    Key::Option(Some(Part::One)),
+    Key::Option(Some(Part::Two)),
+    Key::Option(None),
+    Key::Boolean(true),
+    Key::Boolean(false),
+];
+
+#[test]
+fn test_from_fn_order() {
+    let mut calls = Vec::new();
+
+    let map = TotalMap::from_fn(|k: Key| {
+        calls.push(k);
+        calls.len()
+    });
+
+    assert_eq!(calls, KEYS);
+    assert_eq!(map.keys().collect::<Vec<_>>(), KEYS);
+    assert_eq!(
+        map.values().copied().collect::<Vec<_>>(),
+        (1..=KEYS.len()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_literal() {
+    let map: TotalMap<Key, &str> = total_map! {
+        Key::Simple => "simple",
+        Key::Composite(Part::One) => "one",
+        Key::Composite(Part::Two) => "two",
+        Key::Singleton(()) => "singleton",
+        Key::Option(Some(_)) => "some",
+        Key::Option(None) => "none",
+        Key::Boolean(_) => "boolean",
+    };
+
+    assert_eq!(map[Key::Composite(Part::Two)], "two");
+    assert_eq!(map[Key::Option(Some(Part::One))], "some");
+    assert_eq!(map[Key::Boolean(false)], "boolean");
+}
+
+#[test]
+fn test_index_mut_and_iter_mut() {
+    let mut map = TotalMap::<Key, u32>::from_fn(|_| 0);
+
+    map[Key::Option(None)] = 5;
+    *map.get_mut(Key::Boolean(true)) += 1;
+
+    for (k, v) in map.iter_mut() {
+        if let Key::Composite(_) = k {
+            *v = 2;
+        }
+    }
+
+    assert_eq!(map[Key::Option(None)], 5);
+    assert_eq!(map[Key::Boolean(true)], 1);
+    assert_eq!(map[Key::Composite(Part::One)], 2);
+    assert_eq!(map[Key::Simple], 0);
+}
+
+#[test]
+fn test_map_and_eq() {
+    let a = TotalMap::from_fn(|k: Key| format!("{:?}", k));
+    let b = a.clone().map(|_, v| v.len());
+    let c = TotalMap::from_fn(|k: Key| format!("{:?}", k).len());
+
+    assert_eq!(b, c);
+    assert_eq!(b[Key::Simple], "Simple".len());
+    assert_ne!(b, TotalMap::default());
+}
+
+#[test]
+fn test_map_conversions() {
+    let mut map = Map::new();
+
+    for (n, k) in KEYS.iter().enumerate().skip(1) {
+        map.insert(*k, n);
+    }
+
+    let error = TotalMap::try_from(map.clone()).unwrap_err();
+    assert_eq!(error.key(), Key::Simple);
+
+    map.insert(Key::Simple, 0);
+    let total = TotalMap::try_from(map.clone()).unwrap();
+    assert_eq!(
+        total.values().copied().collect::<Vec<_>>(),
+        (0..9).collect::<Vec<_>>()
+    );
+
+    assert_eq!(Map::from(total), map);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hashed_key_still_maps() {
+    let mut map = Map::new();
+    map.insert(Hashed::Number(3), 3);
+    map.insert(Hashed::First, 1);
+    assert_eq!(map.len(), 2);
+}
@@ -0,0 +1,125 @@
+use fixed_map::key::Enumerable;
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Layer {
+    Top,
+    Bottom,
+}
+
+#[test]
+fn test_pair() {
+    let mut map = Map::new();
+
+    assert_eq!(map.insert((Side::Left, Layer::Top), 1), None);
+    assert_eq!(map.insert((Side::Right, Layer::Bottom), 2), None);
+    assert_eq!(map.insert((Side::Left, Layer::Top), 3), Some(1));
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get((Side::Left, Layer::Top)), Some(&3));
+    assert_eq!(map.get((Side::Left, Layer::Bottom)), None);
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![
+            ((Side::Left, Layer::Top), &3),
+            ((Side::Right, Layer::Bottom), &2),
+        ]
+    );
+
+    assert_eq!(map.remove((Side::Left, Layer::Top)), Some(3));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_triple() {
+    let mut map = Map::new();
+    map.insert((Side::Left, Layer::Top, true), 1);
+    map.insert((Side::Left, Layer::Top, false), 2);
+    map.insert((Side::Right, Layer::Top, true), 3);
+
+    assert_eq!(map.get((Side::Left, Layer::Top, true)), Some(&1));
+    assert_eq!(map.get((Side::Left, Layer::Top, false)), Some(&2));
+    assert_eq!(map.get((Side::Left, Layer::Bottom, false)), None);
+    assert_eq!(map.get((Side::Right, Layer::Top, true)), Some(&3));
+
+    assert_eq!(
+        map.keys().collect::<Vec<_>>(),
+        vec![
+            (Side::Left, Layer::Top, true),
+            (Side::Left, Layer::Top, false),
+            (Side::Right, Layer::Top, true),
+        ]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_eight_elements() {
+    type Wide = (Side, Layer, bool, Option<Side>, (), u32, &'static str, bool);
+
+    let key: Wide = (
+        Side::Right,
+        Layer::Bottom,
+        false,
+        Some(Side::Left),
+        (),
+        42,
+        "foo",
+        true,
+    );
+
+    let mut map = Map::new();
+    map.insert(key, "value");
+
+    assert_eq!(map.get(key), Some(&"value"));
+    assert_eq!(
+        map.get((key.0, key.1, key.2, key.3, key.4, 43, key.6, key.7)),
+        None
+    );
+    assert_eq!(
+        map.get((key.0, key.1, key.2, key.3, key.4, key.5, "bar", key.7)),
+        None
+    );
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec![(key, &"value")]);
+}
+
+#[test]
+fn test_set() {
+    let mut set = Set::new();
+    set.insert((Layer::Top, Side::Left, Some(true)));
+    set.insert((Layer::Bottom, Side::Right, None));
+
+    assert!(set.contains((Layer::Top, Side::Left, Some(true))));
+    assert!(!set.contains((Layer::Top, Side::Left, Some(false))));
+    assert!(set.contains((Layer::Bottom, Side::Right, None)));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_enumerable() {
+    type Triple = (Side, Layer, bool);
+
+    assert_eq!(Triple::COUNT, 8);
+    assert_eq!((Side::Left, Layer::Top, true).ordinal(), 0);
+    assert_eq!((Side::Left, Layer::Bottom, false).ordinal(), 3);
+    assert_eq!(
+        Triple::from_ordinal(5),
+        Some((Side::Right, Layer::Top, false))
+    );
+    assert_eq!(Triple::from_ordinal(8), None);
+
+    let mut map = Map::new();
+
+    for key in Triple::all().rev() {
+        map.insert(key, key.ordinal());
+    }
+
+    assert!(map.iter().map(|(_, &ordinal)| ordinal).eq(0..Triple::COUNT));
+}
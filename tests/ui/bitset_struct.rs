@@ -0,0 +1,10 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+#[key(bitset)]
+struct Cell {
+    top: bool,
+    left: bool,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+enum Key {
+    #[key(bitset)]
+    First,
+    Second,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use fixed_map::key::Enumerable;
+
+const COUNT: usize = <[u8; 8]>::COUNT;
+
+fn main() {
+    let _ = COUNT;
+}
@@ -0,0 +1,10 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+#[key(crate)]
+enum Key {
+    First,
+    Second,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+enum Never {}
+
+fn main() {}
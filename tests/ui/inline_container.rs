@@ -0,0 +1,10 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+#[key(inline = 4)]
+enum Key {
+    First,
+    Second(u32),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+enum Key {
+    #[key(inline = 4)]
+    First,
+    Second,
+}
+
+fn main() {}
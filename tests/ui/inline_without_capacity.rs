@@ -0,0 +1,10 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+enum Key {
+    First,
+    #[key(inline)]
+    Second(u32),
+}
+
+fn main() {}
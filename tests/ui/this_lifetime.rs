@@ -0,0 +1,9 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+enum Name<'this> {
+    Anonymous,
+    Named(&'this str),
+}
+
+fn main() {}
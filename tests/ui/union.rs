@@ -0,0 +1,9 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+union Bits {
+    small: u8,
+    large: u32,
+}
+
+fn main() {}
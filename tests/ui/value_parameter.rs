@@ -0,0 +1,9 @@
+use fixed_map::Key;
+
+#[derive(Clone, Copy, Key)]
+enum Slot<V> {
+    Empty,
+    Full(V),
+}
+
+fn main() {}
@@ -0,0 +1,194 @@
+use fixed_map::key::Enumerable;
+#[cfg(feature = "alloc")]
+use fixed_map::map::{Entry, OccupiedEntry, VacantEntry};
+#[cfg(feature = "alloc")]
+use fixed_map::Set;
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Side {
+    Left,
+    Right,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Key {
+    Empty,
+    Pair(Side, bool),
+    Triple(Side, Option<Side>, bool),
+    Named { side: Side, top: bool },
+    Number(Side, u32),
+    Single { side: Side },
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_multi_field_variants() {
+    let mut map = Map::new();
+
+    assert_eq!(map.insert(Key::Pair(Side::Left, true), 1), None);
+    assert_eq!(map.insert(Key::Pair(Side::Left, false), 2), None);
+    assert_eq!(map.insert(Key::Triple(Side::Right, None, true), 3), None);
+    assert_eq!(map.insert(Key::Number(Side::Left, 42), 4), None);
+    assert_eq!(map.insert(Key::Pair(Side::Left, true), 5), Some(1));
+
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.get(Key::Pair(Side::Left, true)), Some(&5));
+    assert_eq!(map.get(Key::Pair(Side::Right, true)), None);
+    assert_eq!(map.get(Key::Triple(Side::Right, None, true)), Some(&3));
+    assert_eq!(
+        map.get(Key::Triple(Side::Right, Some(Side::Left), true)),
+        None
+    );
+    assert_eq!(map.get(Key::Number(Side::Left, 42)), Some(&4));
+    assert_eq!(map.get(Key::Number(Side::Left, 43)), None);
+
+    assert_eq!(map.remove(Key::Pair(Side::Left, false)), Some(2));
+    assert_eq!(map.remove(Key::Pair(Side::Left, false)), None);
+    assert_eq!(map.len(), 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_named_field_variants() {
+    let mut map = Map::new();
+
+    map.insert(
+        Key::Named {
+            side: Side::Right,
+            top: false,
+        },
+        1,
+    );
+    map.insert(Key::Single { side: Side::Left }, 2);
+
+    assert_eq!(
+        map.get(Key::Named {
+            side: Side::Right,
+            top: false
+        }),
+        Some(&1)
+    );
+    assert_eq!(
+        map.get(Key::Named {
+            side: Side::Right,
+            top: true
+        }),
+        None
+    );
+    assert_eq!(map.get(Key::Single { side: Side::Left }), Some(&2));
+    assert_eq!(map.get(Key::Single { side: Side::Right }), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iter_and_entry() {
+    let mut map = Map::new();
+    map.insert(
+        Key::Named {
+            side: Side::Left,
+            top: true,
+        },
+        3,
+    );
+    map.insert(Key::Pair(Side::Right, false), 2);
+    map.insert(Key::Empty, 1);
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![
+            (Key::Empty, &1),
+            (Key::Pair(Side::Right, false), &2),
+            (
+                Key::Named {
+                    side: Side::Left,
+                    top: true
+                },
+                &3
+            ),
+        ]
+    );
+
+    match map.entry(Key::Triple(Side::Left, Some(Side::Right), false)) {
+        Entry::Vacant(entry) => {
+            assert_eq!(
+                entry.key(),
+                Key::Triple(Side::Left, Some(Side::Right), false)
+            );
+            entry.insert(4);
+        }
+        Entry::Occupied(..) => panic!("expected a vacant entry"),
+    }
+
+    match map.entry(Key::Pair(Side::Right, false)) {
+        Entry::Occupied(entry) => {
+            assert_eq!(entry.key(), Key::Pair(Side::Right, false));
+            assert_eq!(entry.remove(), 2);
+        }
+        Entry::Vacant(..) => panic!("expected an occupied entry"),
+    }
+
+    map.retain(|key, _| !matches!(key, Key::Named { .. }));
+
+    assert_eq!(
+        map.into_iter().collect::<Vec<_>>(),
+        vec![
+            (Key::Empty, 1),
+            (Key::Triple(Side::Left, Some(Side::Right), false), 4),
+        ]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_set() {
+    let mut set = Set::new();
+    set.insert(Key::Pair(Side::Left, true));
+    set.insert(Key::Named {
+        side: Side::Right,
+        top: true,
+    });
+
+    assert!(set.contains(Key::Pair(Side::Left, true)));
+    assert!(!set.contains(Key::Pair(Side::Left, false)));
+    assert!(set.contains(Key::Named {
+        side: Side::Right,
+        top: true
+    }));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Finite {
+    Unit,
+    Pair(Side, bool),
+    Named { a: bool, b: Side },
+}
+
+#[test]
+fn test_enumerable() {
+    assert_eq!(Finite::COUNT, 9);
+    assert_eq!(Finite::Pair(Side::Right, false).ordinal(), 4);
+    assert_eq!(
+        Finite::from_ordinal(6),
+        Some(Finite::Named {
+            a: true,
+            b: Side::Right
+        })
+    );
+
+    let all = Finite::all().collect::<Vec<_>>();
+    assert_eq!(all.len(), Finite::COUNT);
+
+    for (ordinal, key) in all.into_iter().enumerate() {
+        assert_eq!(key.ordinal(), ordinal);
+    }
+
+    let mut map = Map::new();
+
+    for key in Finite::all().rev() {
+        map.insert(key, key.ordinal());
+    }
+
+    assert!(map.iter().map(|(_, &ordinal)| ordinal).eq(0..Finite::COUNT));
+}